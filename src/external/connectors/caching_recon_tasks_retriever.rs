@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::internal::{
+    interfaces::recon_tasks_repo::ReconTasksDetailsRetrieverInterface,
+    shared_reconciler_rust_libraries::models::{
+        entities::app_errors::AppError,
+        view_models::recon_task_response_details::ReconTaskResponseDetails,
+    },
+};
+
+struct CachedReconTaskDetails {
+    recon_task_details: ReconTaskResponseDetails,
+    date_cached: Instant,
+}
+
+/**
+keeps recently retrieved recon task details around, so the chunks of one upload
+dont each go back to the recon tasks service
+
+concurrent misses for the same task wait for a single lookup instead of each making their own
+ */
+pub struct CachingReconTasksRetriever {
+    //where details are retrieved from on a miss
+    recon_tasks_retriever: Box<dyn ReconTasksDetailsRetrieverInterface>,
+
+    //how long cached details are trusted for
+    time_to_live: Duration,
+
+    //the most tasks kept at once, the oldest are evicted first
+    max_entries: usize,
+
+    cached_task_details: Mutex<HashMap<String, CachedReconTaskDetails>>,
+
+    //one lock per task that has a lookup in flight
+    in_flight_lookups: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+#[async_trait]
+impl ReconTasksDetailsRetrieverInterface for CachingReconTasksRetriever {
+    async fn get_recon_task_details(
+        &self,
+        task_id: &String,
+    ) -> Result<ReconTaskResponseDetails, AppError> {
+        if let Some(recon_task_details) = self.get_cached(task_id).await {
+            return Ok(recon_task_details);
+        }
+
+        let lookup_lock = {
+            let mut in_flight_lookups = self.in_flight_lookups.lock().await;
+            in_flight_lookups
+                .entry(task_id.clone())
+                .or_insert_with(|| Arc::new(Mutex::new(())))
+                .clone()
+        };
+        let _lookup_guard = lookup_lock.lock().await;
+
+        //whoever held the lock before us may have already filled the cache
+        if let Some(recon_task_details) = self.get_cached(task_id).await {
+            return Ok(recon_task_details);
+        }
+
+        let lookup_result = self
+            .recon_tasks_retriever
+            .get_recon_task_details(task_id)
+            .await;
+
+        //errors are not cached, the next caller tries again
+        if let Ok(recon_task_details) = &lookup_result {
+            self.put_cached(task_id, recon_task_details.clone()).await;
+        }
+
+        self.in_flight_lookups.lock().await.remove(task_id);
+
+        return lookup_result;
+    }
+
+    async fn invalidate_recon_task_details(&self, task_id: &String) {
+        self.cached_task_details.lock().await.remove(task_id);
+    }
+}
+
+impl CachingReconTasksRetriever {
+    pub fn new(
+        recon_tasks_retriever: Box<dyn ReconTasksDetailsRetrieverInterface>,
+        time_to_live: Duration,
+        max_entries: usize,
+    ) -> CachingReconTasksRetriever {
+        return CachingReconTasksRetriever {
+            recon_tasks_retriever,
+            time_to_live,
+            max_entries: max_entries.max(1),
+            cached_task_details: Mutex::new(HashMap::new()),
+            in_flight_lookups: Mutex::new(HashMap::new()),
+        };
+    }
+
+    async fn get_cached(&self, task_id: &String) -> Option<ReconTaskResponseDetails> {
+        let cached_task_details = self.cached_task_details.lock().await;
+
+        return cached_task_details
+            .get(task_id)
+            .filter(|cached| cached.date_cached.elapsed() < self.time_to_live)
+            .map(|cached| cached.recon_task_details.clone());
+    }
+
+    async fn put_cached(&self, task_id: &String, recon_task_details: ReconTaskResponseDetails) {
+        let mut cached_task_details = self.cached_task_details.lock().await;
+
+        if !cached_task_details.contains_key(task_id)
+            && cached_task_details.len() >= self.max_entries
+        {
+            //make room, dropping expired entries before live ones
+            let time_to_live = self.time_to_live;
+            cached_task_details.retain(|_, cached| cached.date_cached.elapsed() < time_to_live);
+
+            if cached_task_details.len() >= self.max_entries {
+                let oldest_task_id = cached_task_details
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.date_cached)
+                    .map(|(oldest_task_id, _)| oldest_task_id.clone());
+
+                if let Some(oldest_task_id) = oldest_task_id {
+                    cached_task_details.remove(&oldest_task_id);
+                }
+            }
+        }
+
+        cached_task_details.insert(
+            task_id.clone(),
+            CachedReconTaskDetails {
+                recon_task_details,
+                date_cached: Instant::now(),
+            },
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::internal::{
+    interfaces::recon_tasks_repo::{
+        MockReconTasksDetailsRetrieverInterface, ReconTasksDetailsRetrieverInterface,
+    },
+    shared_reconciler_rust_libraries::models::{
+        entities::{
+            app_errors::{AppError, AppErrorKind},
+            file_chunk_queue::FileChunkQueue,
+            recon_tasks_models::{ReconTaskDetails, ReconciliationConfigs},
+        },
+        view_models::recon_task_response_details::ReconTaskResponseDetails,
+    },
+};
+
+use super::caching_recon_tasks_retriever::CachingReconTasksRetriever;
+
+//answers every lookup after a short pause, counting how many it was asked for
+struct SlowReconTasksRetriever {
+    lookups: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ReconTasksDetailsRetrieverInterface for SlowReconTasksRetriever {
+    async fn get_recon_task_details(
+        &self,
+        task_id: &String,
+    ) -> Result<ReconTaskResponseDetails, AppError> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Ok(dummy_recon_task_details(task_id))
+    }
+
+    async fn invalidate_recon_task_details(&self, _task_id: &String) {}
+}
+
+#[actix_rt::test]
+async fn given_task_details_cached_does_not_look_them_up_again() {
+    let mut mock_recon_tasks_repo = Box::new(MockReconTasksDetailsRetrieverInterface::new());
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(1)
+        .returning(|task_id| Ok(dummy_recon_task_details(task_id)));
+
+    let sut = CachingReconTasksRetriever::new(mock_recon_tasks_repo, Duration::from_secs(60), 10);
+
+    for _ in 0..3 {
+        let actual = sut
+            .get_recon_task_details(&String::from("task-1234"))
+            .await
+            .unwrap();
+        assert_eq!(actual.task_id, "task-1234");
+    }
+}
+
+#[actix_rt::test]
+async fn given_cached_task_details_expired_looks_them_up_again() {
+    let mut mock_recon_tasks_repo = Box::new(MockReconTasksDetailsRetrieverInterface::new());
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(2)
+        .returning(|task_id| Ok(dummy_recon_task_details(task_id)));
+
+    let sut = CachingReconTasksRetriever::new(mock_recon_tasks_repo, Duration::ZERO, 10);
+
+    for _ in 0..2 {
+        let actual = sut.get_recon_task_details(&String::from("task-1234")).await;
+        assert!(actual.is_ok());
+    }
+}
+
+#[actix_rt::test]
+async fn given_max_entries_reached_evicts_the_oldest_task() {
+    let mut mock_recon_tasks_repo = Box::new(MockReconTasksDetailsRetrieverInterface::new());
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(4)
+        .returning(|task_id| Ok(dummy_recon_task_details(task_id)));
+
+    let sut = CachingReconTasksRetriever::new(mock_recon_tasks_repo, Duration::from_secs(60), 2);
+
+    //task-1 is pushed out by task-3, so it is looked up twice
+    for task_id in ["task-1", "task-2", "task-3", "task-1"] {
+        let actual = sut.get_recon_task_details(&String::from(task_id)).await;
+        assert!(actual.is_ok());
+    }
+}
+
+#[actix_rt::test]
+async fn given_task_details_invalidated_looks_them_up_again() {
+    let mut mock_recon_tasks_repo = Box::new(MockReconTasksDetailsRetrieverInterface::new());
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(2)
+        .returning(|task_id| Ok(dummy_recon_task_details(task_id)));
+
+    let sut = CachingReconTasksRetriever::new(mock_recon_tasks_repo, Duration::from_secs(60), 10);
+    let task_id = String::from("task-1234");
+
+    let _ = sut.get_recon_task_details(&task_id).await;
+    sut.invalidate_recon_task_details(&task_id).await;
+    let actual = sut.get_recon_task_details(&task_id).await;
+
+    assert!(actual.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_lookup_fails_does_not_cache_the_error() {
+    let mut mock_recon_tasks_repo = Box::new(MockReconTasksDetailsRetrieverInterface::new());
+    let mut sequence = mockall::Sequence::new();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                "unable to connect".to_string(),
+            ))
+        });
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|task_id| Ok(dummy_recon_task_details(task_id)));
+
+    let sut = CachingReconTasksRetriever::new(mock_recon_tasks_repo, Duration::from_secs(60), 10);
+    let task_id = String::from("task-1234");
+
+    let first_attempt = sut.get_recon_task_details(&task_id).await;
+    let second_attempt = sut.get_recon_task_details(&task_id).await;
+
+    assert!(first_attempt.is_err());
+    assert!(second_attempt.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_concurrent_misses_for_same_task_makes_one_lookup() {
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let slow_recon_tasks_repo = Box::new(SlowReconTasksRetriever {
+        lookups: lookups.clone(),
+    });
+
+    let sut = CachingReconTasksRetriever::new(slow_recon_tasks_repo, Duration::from_secs(60), 10);
+    let task_id = String::from("task-1234");
+
+    let (first, second, third) = tokio::join!(
+        sut.get_recon_task_details(&task_id),
+        sut.get_recon_task_details(&task_id),
+        sut.get_recon_task_details(&task_id)
+    );
+
+    assert!(first.is_ok() && second.is_ok() && third.is_ok());
+    assert_eq!(lookups.load(Ordering::SeqCst), 1);
+}
+
+fn dummy_recon_task_details(task_id: &String) -> ReconTaskResponseDetails {
+    ReconTaskResponseDetails {
+        task_id: task_id.clone(),
+        task_details: ReconTaskDetails {
+            id: task_id.clone(),
+            primary_file_id: Some(String::from("src-file-1234")),
+            comparison_file_id: Some(String::from("cmp-file-1234")),
+            is_done: false,
+            has_begun: true,
+            comparison_pairs: vec![],
+            recon_config: ReconciliationConfigs {
+                should_check_for_duplicate_records_in_comparison_file: true,
+                should_reconciliation_be_case_sensitive: true,
+                should_ignore_white_space: true,
+                should_do_reverse_reconciliation: true,
+            },
+            primary_file_chunks_queue_info: new_file_chunk_queue("src-file-chunks-queue-1"),
+            comparison_file_chunks_queue_info: new_file_chunk_queue("cmp-file-chunks-queue-1"),
+            recon_results_queue_info: new_file_chunk_queue("recon-results-queue-1"),
+        },
+        primary_file_metadata: None,
+        comparison_file_metadata: None,
+    }
+}
+
+fn new_file_chunk_queue(topic_id: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: String::from(topic_id),
+        last_acknowledged_id: Option::None,
+    }
+}
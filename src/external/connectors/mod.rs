@@ -1 +1,7 @@
+pub mod caching_recon_tasks_retriever;
 pub mod recon_tasks_service_connector;
+pub mod recon_tasks_service_health_check;
+
+#[cfg(test)]
+#[path = "./caching_recon_tasks_retriever_tests.rs"]
+mod caching_recon_tasks_retriever_tests;
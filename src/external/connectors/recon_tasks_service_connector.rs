@@ -1,7 +1,11 @@
+use std::time::Instant;
+
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::internal::{
     interfaces::recon_tasks_repo::ReconTasksDetailsRetrieverInterface,
+    metrics::app_metrics::{outcome_label, RECON_TASK_LOOKUP_DURATION},
     shared_reconciler_rust_libraries::models::{
         entities::app_errors::AppError,
         view_models::recon_task_response_details::ReconTaskResponseDetails,
@@ -15,13 +19,25 @@ pub struct ReconTasksServiceConnector {
 
 #[async_trait]
 impl ReconTasksDetailsRetrieverInterface for ReconTasksServiceConnector {
+    #[instrument(skip(self))]
     async fn get_recon_task_details(
         &self,
         task_id: &String,
     ) -> Result<ReconTaskResponseDetails, AppError> {
+        let started_at = Instant::now();
+
         let result = self.recon_tasks_microservice_client.get_recon_task(task_id).await;
+
+        RECON_TASK_LOOKUP_DURATION
+            .with_label_values(&[outcome_label(&result)])
+            .observe(started_at.elapsed().as_secs_f64());
+
         return result;
     }
+
+    async fn invalidate_recon_task_details(&self, _task_id: &String) {
+        //nothing is held between lookups
+    }
 }
 
 impl ReconTasksServiceConnector {
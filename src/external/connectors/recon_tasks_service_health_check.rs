@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+
+use crate::internal::interfaces::health_check::DependencyHealthCheckInterface;
+use crate::internal::shared_reconciler_rust_libraries::common::utils::app_error_with_msg;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+const RECON_TASKS_DEPENDENCY_NAME: &'static str = "recon-tasks-service";
+
+/**
+checks that the recon tasks service can be reached through dapr service invocation,
+the same way the recon tasks client reaches it
+ */
+pub struct ReconTasksServiceHealthCheck {
+    pub host: String,
+
+    pub recon_tasks_service_app_id: String,
+
+    pub health_check_path: String,
+
+    pub http_client: reqwest::Client,
+}
+
+#[async_trait]
+impl DependencyHealthCheckInterface for ReconTasksServiceHealthCheck {
+    fn dependency_name(&self) -> String {
+        return RECON_TASKS_DEPENDENCY_NAME.to_string();
+    }
+
+    /**
+    # Errors
+
+    This function will return an error if the service cant be reached, or dapr answers
+    with a server error (which is what it does when it cant reach the app behind it)
+     */
+    async fn check_health(&self) -> Result<(), AppError> {
+        let health_check_url = format!(
+            "{}/v1.0/invoke/{}/method/{}",
+            self.host.trim_end_matches('/'),
+            self.recon_tasks_service_app_id,
+            self.health_check_path.trim_start_matches('/')
+        );
+
+        let response = match self.http_client.get(&health_check_url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(AppError::new(AppErrorKind::ConnectionError, e.to_string()));
+            }
+        };
+
+        //any answer short of a server error means the service is up and listening
+        if response.status().is_server_error() {
+            let error_msg = format!(
+                "recon tasks service answered its health check with {}",
+                response.status()
+            );
+            return app_error_with_msg(AppErrorKind::ConnectionError, &error_msg);
+        }
+
+        return Ok(());
+    }
+}
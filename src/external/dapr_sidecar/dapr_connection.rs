@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
+use tokio::sync::Mutex;
+use tonic::{
+    transport::{Channel as TonicChannel, Endpoint},
+    Code, Status,
+};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+//how long opening a channel or probing it may take before the sidecar counts as unreachable
+const DAPR_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const DAPR_HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+//the generated gRPC client, its calls fail with a tonic Status whose code we can act on
+pub type DaprGrpcClient = DaprClient<TonicChannel>;
+
+/**
+a single, lazily opened gRPC channel to the dapr sidecar
+
+clients handed out are cheap clones that multiplex over the same channel,
+and the channel is dropped and re-opened once the sidecar is reported unavailable
+ */
+pub struct DaprConnection {
+    //the dapr server ip
+    dapr_grpc_server_address: String,
+
+    //the open client, if we have connected already
+    dapr_client: Mutex<Option<DaprGrpcClient>>,
+
+    //held while a channel is being opened, so concurrent callers wait for one connect
+    //instead of each opening their own, without blocking those that already have a client
+    connecting: Mutex<()>,
+}
+
+impl DaprConnection {
+    pub fn new(dapr_grpc_server_address: String) -> DaprConnection {
+        return DaprConnection {
+            dapr_grpc_server_address,
+            dapr_client: Mutex::new(None),
+            connecting: Mutex::new(()),
+        };
+    }
+
+    /**
+    returns a client on the shared channel, connecting first if there is no channel yet
+
+    # Errors
+
+    This function will return an error if the dapr sidecar cant be reached
+     */
+    pub async fn get_client(&self) -> Result<DaprGrpcClient, AppError> {
+        if let Some(client) = self.current_client().await {
+            return Ok(client);
+        }
+
+        let _connecting = self.connecting.lock().await;
+
+        //whoever held the connecting lock before us may have opened the channel already
+        if let Some(client) = self.current_client().await {
+            return Ok(client);
+        }
+
+        let client = self.connect().await?;
+        *self.dapr_client.lock().await = Some(client.clone());
+
+        return Ok(client);
+    }
+
+    /**
+    drops the shared channel if the call failed because the sidecar is unavailable,
+    which is also how a broken channel shows up, so that the next call reconnects
+     */
+    pub async fn handle_error(&self, status: &Status) {
+        if status.code() == Code::Unavailable {
+            self.reset().await;
+        }
+    }
+
+    /**
+    checks that the dapr sidecar answers on the shared channel, dropping the channel
+    if it does not so that a channel that silently went stale is not handed out again
+
+    # Errors
+
+    This function will return an error if the dapr sidecar cant be reached
+     */
+    pub async fn check_health(&self) -> Result<(), AppError> {
+        let mut client = self.get_client().await?;
+
+        let probe_result =
+            tokio::time::timeout(DAPR_HEALTH_PROBE_TIMEOUT, client.get_metadata(())).await;
+
+        return match probe_result {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => {
+                self.reset().await;
+                Err(AppError::new(AppErrorKind::ConnectionError, e.to_string()))
+            }
+            Err(_) => {
+                self.reset().await;
+                let error_msg = format!(
+                    "dapr sidecar did not answer within {} seconds",
+                    DAPR_HEALTH_PROBE_TIMEOUT.as_secs()
+                );
+                Err(AppError::new(AppErrorKind::ConnectionError, error_msg))
+            }
+        };
+    }
+
+    /**
+    keeps checking the dapr sidecar in the background so a dead channel is replaced
+    before the next publish has to find out the hard way
+     */
+    pub fn start_health_checks(self: &Arc<Self>, health_check_interval: Duration) {
+        let dapr_connection = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut health_check_timer = tokio::time::interval(health_check_interval);
+            loop {
+                health_check_timer.tick().await;
+                let _ = dapr_connection.check_health().await;
+            }
+        });
+    }
+
+    async fn current_client(&self) -> Option<DaprGrpcClient> {
+        return self.dapr_client.lock().await.clone();
+    }
+
+    async fn reset(&self) {
+        let mut dapr_client = self.dapr_client.lock().await;
+        *dapr_client = None;
+    }
+
+    async fn connect(&self) -> Result<DaprGrpcClient, AppError> {
+        // Create the client
+        let dapr_grpc_server_address = self.dapr_grpc_server_address.clone();
+
+        let endpoint = match Endpoint::from_shared(dapr_grpc_server_address) {
+            Ok(endpoint) => endpoint.connect_timeout(DAPR_CONNECT_TIMEOUT),
+            Err(e) => return Err(AppError::new(AppErrorKind::ConnectionError, e.to_string())),
+        };
+
+        //connect to dapr
+        let channel_connect_result = endpoint.connect().await;
+
+        //handle the connection result
+        return match channel_connect_result {
+            //connection succeeded
+            Ok(channel) => Ok(DaprClient::new(channel)),
+            //connection failed
+            Err(e) => Err(AppError::new(AppErrorKind::ConnectionError, e.to_string())),
+        };
+    }
+}
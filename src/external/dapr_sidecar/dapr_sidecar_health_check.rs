@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::dapr_sidecar::dapr_connection::DaprConnection;
+use crate::internal::interfaces::health_check::DependencyHealthCheckInterface;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
+
+const DAPR_SIDECAR_DEPENDENCY_NAME: &'static str = "dapr-sidecar";
+
+/**
+checks the same connection to the dapr sidecar that pubsub and the state store publish over
+ */
+pub struct DaprSidecarHealthCheck {
+    pub dapr_connection: Arc<DaprConnection>,
+}
+
+#[async_trait]
+impl DependencyHealthCheckInterface for DaprSidecarHealthCheck {
+    fn dependency_name(&self) -> String {
+        return DAPR_SIDECAR_DEPENDENCY_NAME.to_string();
+    }
+
+    async fn check_health(&self) -> Result<(), AppError> {
+        return self.dapr_connection.check_health().await;
+    }
+}
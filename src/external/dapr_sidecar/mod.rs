@@ -0,0 +1,2 @@
+pub mod dapr_connection;
+pub mod dapr_sidecar_health_check;
@@ -1,2 +1,4 @@
 pub mod pubsub;
 pub mod connectors;
+pub mod dapr_sidecar;
+pub mod state_store;
@@ -1,23 +1,46 @@
-use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
-use dapr::{Client, dapr::dapr::proto::runtime::v1::dapr_client::DaprClient};
-use tonic::transport::Channel as TonicChannel;
+use dapr::dapr::dapr::proto::runtime::v1::PublishEventRequest;
+use serde::Serialize;
+use tonic::Code;
+use tracing::instrument;
 
+use crate::external::dapr_sidecar::dapr_connection::DaprConnection;
 use crate::internal::{
     interfaces::pubsub_repo::PubSubRepositoryInterface,
-    shared_reconciler_rust_libraries::models::entities::{
-        app_errors::{AppError, AppErrorKind},
-        file_upload_chunk::FileUploadChunk,
+    metrics::app_metrics::{
+        error_kind_label, outcome_label, PUBSUB_PUBLISH_DURATION, PUBSUB_PUBLISH_ERRORS,
     },
+    models::entities::{
+        file_upload_completed_event::FileUploadCompletedEvent,
+        file_upload_verification::FileUploadVerificationOutcome,
+        published_file_upload_chunk::PublishedFileUploadChunk,
+        upload_aborted_event::UploadAbortedEvent,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+    telemetry::trace_context::current_trace_metadata,
 };
 use crate::internal::shared_reconciler_rust_libraries::common::utils::app_error;
 
 const DATA_CONTENT_TYPE: &'static str = "json";
+const FILE_UPLOAD_CHUNK_EVENT_TYPE: &'static str = "file_upload_chunk";
+const VERIFICATION_OUTCOME_EVENT_TYPE: &'static str = "file_upload_verification_outcome";
+const FILE_UPLOAD_COMPLETED_EVENT_TYPE: &'static str = "file_upload_completed";
+const UPLOAD_ABORTED_EVENT_TYPE: &'static str = "upload_aborted";
+
+//gRPC status codes that mean the publish may succeed if tried again
+const RETRYABLE_GRPC_STATUS_CODES: [Code; 4] = [
+    Code::Unavailable,
+    Code::DeadlineExceeded,
+    Code::ResourceExhausted,
+    Code::Aborted,
+];
 
 pub struct DaprPubSub {
-    //the dapr server ip
-    pub dapr_grpc_server_address: String,
+    //the shared channel to the dapr sidecar
+    pub dapr_connection: Arc<DaprConnection>,
 
     //the dapr pub sub component name
     pub dapr_pubsub_name: String,
@@ -27,74 +50,155 @@ pub struct DaprPubSub {
 impl PubSubRepositoryInterface for DaprPubSub {
     async fn save_file_upload_chunk_to_primary_file_queue(
         &self,
-        file_upload_chunk: &FileUploadChunk,
+        published_file_chunk: &PublishedFileUploadChunk,
     ) -> Result<String, AppError> {
-        //create a dapr client
-        let mut client = self.get_dapr_connection().await?;
-
-        //call the binding
-        let pubsub_name = self.dapr_pubsub_name.clone();
-        let pubsub_topic = file_upload_chunk.primary_file_chunks_queue.topic_id.clone();
-        let data = serde_json::to_vec(&file_upload_chunk).unwrap();
-        let metadata = None::<HashMap<String, String>>;
-        let binding_response = client
-            .publish_event(pubsub_name, pubsub_topic, DATA_CONTENT_TYPE.to_string(), data, metadata)
-            .await;
+        let pubsub_topic = published_file_chunk
+            .file_upload_chunk
+            .primary_file_chunks_queue
+            .topic_id
+            .clone();
 
-        //handle the bindings response
-        return match binding_response {
-            //success
-            Ok(_) => Ok(file_upload_chunk.clone().id),
-            //failure
-            Err(e) => app_error(AppErrorKind::InternalError, Box::new(e)),
-        };
+        self.publish_file_upload_chunk(pubsub_topic, published_file_chunk).await
     }
 
     async fn save_file_upload_chunk_to_comparison_file_queue(
         &self,
-        file_upload_chunk: &FileUploadChunk,
+        published_file_chunk: &PublishedFileUploadChunk,
     ) -> Result<String, AppError> {
-        //create a dapr client
-        let mut client = self.get_dapr_connection().await?;
-
-        //call the binding
-        let pubsub_name = self.dapr_pubsub_name.clone();
-        let pubsub_topic = file_upload_chunk
+        let pubsub_topic = published_file_chunk
+            .file_upload_chunk
             .comparison_file_chunks_queue
             .topic_id
             .clone();
-        let data = serde_json::to_vec(&file_upload_chunk).unwrap();
-        let metadata = None::<HashMap<String, String>>;
 
-        let binding_response = client
-            .publish_event(pubsub_name, pubsub_topic, DATA_CONTENT_TYPE.to_string(), data, metadata)
-            .await;
+        self.publish_file_upload_chunk(pubsub_topic, published_file_chunk).await
+    }
+
+    async fn publish_file_upload_verification_outcome(
+        &self,
+        pubsub_topic: &String,
+        verification_outcome: &FileUploadVerificationOutcome,
+    ) -> Result<(), AppError> {
+        self.publish_event(
+            VERIFICATION_OUTCOME_EVENT_TYPE,
+            pubsub_topic.clone(),
+            verification_outcome,
+        )
+            .await
+    }
+
+    async fn publish_file_upload_completed_event(
+        &self,
+        pubsub_topic: &String,
+        file_upload_completed_event: &FileUploadCompletedEvent,
+    ) -> Result<(), AppError> {
+        self.publish_event(
+            FILE_UPLOAD_COMPLETED_EVENT_TYPE,
+            pubsub_topic.clone(),
+            file_upload_completed_event,
+        )
+            .await
+    }
+
+    async fn publish_upload_aborted_event(
+        &self,
+        pubsub_topic: &String,
+        upload_aborted_event: &UploadAbortedEvent,
+    ) -> Result<(), AppError> {
+        self.publish_event(
+            UPLOAD_ABORTED_EVENT_TYPE,
+            pubsub_topic.clone(),
+            upload_aborted_event,
+        )
+            .await
+    }
+}
+
+impl DaprPubSub {
+    async fn publish_file_upload_chunk(
+        &self,
+        pubsub_topic: String,
+        published_file_chunk: &PublishedFileUploadChunk,
+    ) -> Result<String, AppError> {
+        self.publish_event(FILE_UPLOAD_CHUNK_EVENT_TYPE, pubsub_topic, published_file_chunk)
+            .await?;
+
+        return Ok(published_file_chunk.file_upload_chunk.id.clone());
+    }
+
+    /**
+    publishes the event, timing it under its event type since topics can be named per task
+
+    # Errors
+
+    This function will return an error if the dapr sidecar cant be reached or rejects the event
+     */
+    #[instrument(skip(self, event))]
+    async fn publish_event<T: Serialize + Sync>(
+        &self,
+        event_type: &'static str,
+        pubsub_topic: String,
+        event: &T,
+    ) -> Result<(), AppError> {
+        let started_at = Instant::now();
+
+        let publish_result = self.try_publish_event(pubsub_topic, event).await;
+
+        PUBSUB_PUBLISH_DURATION
+            .with_label_values(&[event_type, outcome_label(&publish_result)])
+            .observe(started_at.elapsed().as_secs_f64());
+
+        if let Err(e) = &publish_result {
+            PUBSUB_PUBLISH_ERRORS
+                .with_label_values(&[event_type, error_kind_label(&e.kind).as_str()])
+                .inc();
+        }
+
+        return publish_result;
+    }
+
+    async fn try_publish_event<T: Serialize + Sync>(
+        &self,
+        pubsub_topic: String,
+        event: &T,
+    ) -> Result<(), AppError> {
+        //get a client on the shared dapr channel
+        let mut client = self.dapr_connection.get_client().await?;
+
+        //call the binding
+        let publish_event_request = PublishEventRequest {
+            pubsub_name: self.dapr_pubsub_name.clone(),
+            topic: pubsub_topic,
+            data: serde_json::to_vec(event).unwrap(),
+            data_content_type: DATA_CONTENT_TYPE.to_string(),
+            //carry the trace on to whoever consumes the event
+            metadata: current_trace_metadata(),
+        };
+        let binding_response = client.publish_event(publish_event_request).await;
 
         //handle the bindings response
         return match binding_response {
             //success
-            Ok(_) => Ok(file_upload_chunk.clone().id),
+            Ok(_) => Ok(()),
             //failure
-            Err(e) => app_error(AppErrorKind::InternalError, Box::new(e)),
+            Err(e) => {
+                //reconnect on the next publish if the channel itself broke
+                self.dapr_connection.handle_error(&e).await;
+
+                app_error(error_kind_of(e.code()), Box::new(e))
+            }
         };
     }
 }
 
-impl DaprPubSub {
-    async fn get_dapr_connection(&self) -> Result<Client<DaprClient<TonicChannel>>, AppError> {
-        // Create the client
-        let dapr_grpc_server_address = self.dapr_grpc_server_address.clone();
-
-        //connect to dapr
-        let client_connect_result =
-            dapr::Client::<dapr::client::TonicClient>::connect(dapr_grpc_server_address).await;
-
-        //handle the connection result
-        return match client_connect_result {
-            //connection succeeded
-            Ok(s) => Ok(s),
-            //connection failed
-            Err(e) => Err(AppError::new(AppErrorKind::ConnectionError, e.to_string())),
-        };
+/**
+a publish that failed with one of the retryable gRPC status codes is reported as a connection
+error, which is what the retrying pub sub retries on
+ */
+pub fn error_kind_of(status_code: Code) -> AppErrorKind {
+    if RETRYABLE_GRPC_STATUS_CODES.contains(&status_code) {
+        return AppErrorKind::ConnectionError;
     }
+
+    return AppErrorKind::InternalError;
 }
@@ -0,0 +1,28 @@
+use tonic::Code;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind;
+
+use super::dapr_pubsub::error_kind_of;
+
+#[test]
+fn given_retryable_grpc_status_code_reports_connection_error() {
+    for status_code in [
+        Code::Unavailable,
+        Code::DeadlineExceeded,
+        Code::ResourceExhausted,
+        Code::Aborted,
+    ] {
+        let actual = error_kind_of(status_code);
+
+        assert!(matches!(actual, AppErrorKind::ConnectionError));
+    }
+}
+
+#[test]
+fn given_non_retryable_grpc_status_code_reports_internal_error() {
+    for status_code in [Code::InvalidArgument, Code::NotFound, Code::Internal] {
+        let actual = error_kind_of(status_code);
+
+        assert!(matches!(actual, AppErrorKind::InternalError));
+    }
+}
@@ -1 +1,10 @@
 pub mod dapr_pubsub;
+pub mod retrying_pubsub;
+
+#[cfg(test)]
+#[path = "./dapr_pubsub_tests.rs"]
+mod dapr_pubsub_tests;
+
+#[cfg(test)]
+#[path = "./retrying_pubsub_tests.rs"]
+mod retrying_pubsub_tests;
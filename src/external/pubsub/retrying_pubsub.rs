@@ -0,0 +1,153 @@
+use std::future::Future;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rand::Rng;
+
+use crate::internal::{
+    interfaces::pubsub_repo::PubSubRepositoryInterface,
+    models::entities::{
+        file_upload_completed_event::FileUploadCompletedEvent,
+        file_upload_verification::FileUploadVerificationOutcome,
+        published_file_upload_chunk::PublishedFileUploadChunk,
+        upload_aborted_event::UploadAbortedEvent,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+/**
+how often, and how patiently, a failed publish is retried
+ */
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    //total number of publish attempts, including the first one
+    pub max_attempts: u32,
+
+    //delay before the first retry, doubled on every retry after it
+    pub base_delay: Duration,
+
+    //the delay never grows past this
+    pub max_delay: Duration,
+
+    //fraction (0.0 - 1.0) of each delay that is randomised away so retries dont stampede
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /**
+    only connection errors are worth retrying, the pubsub repository decides which
+    failures of its backend count as one
+     */
+    pub fn is_retryable(&self, error: &AppError) -> bool {
+        return matches!(error.kind, AppErrorKind::ConnectionError);
+    }
+
+    pub fn delay_before_retry(&self, retry_number: u32) -> Duration {
+        let exponential_delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry_number.saturating_sub(1)));
+        let capped_delay = exponential_delay.min(self.max_delay);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return capped_delay;
+        }
+
+        let random_fraction: f64 = rand::thread_rng().gen_range(0.0..=jitter);
+        return capped_delay.mul_f64(1.0 - random_fraction);
+    }
+}
+
+/**
+retries publishes of any pubsub repository according to a retry policy
+ */
+pub struct RetryingPubSub {
+    pub pubsub: Box<dyn PubSubRepositoryInterface>,
+    pub retry_policy: RetryPolicy,
+}
+
+#[async_trait]
+impl PubSubRepositoryInterface for RetryingPubSub {
+    async fn save_file_upload_chunk_to_primary_file_queue(
+        &self,
+        published_file_chunk: &PublishedFileUploadChunk,
+    ) -> Result<String, AppError> {
+        self.with_retries(|| {
+            self.pubsub
+                .save_file_upload_chunk_to_primary_file_queue(published_file_chunk)
+        })
+        .await
+    }
+
+    async fn save_file_upload_chunk_to_comparison_file_queue(
+        &self,
+        published_file_chunk: &PublishedFileUploadChunk,
+    ) -> Result<String, AppError> {
+        self.with_retries(|| {
+            self.pubsub
+                .save_file_upload_chunk_to_comparison_file_queue(published_file_chunk)
+        })
+        .await
+    }
+
+    async fn publish_file_upload_verification_outcome(
+        &self,
+        pubsub_topic: &String,
+        verification_outcome: &FileUploadVerificationOutcome,
+    ) -> Result<(), AppError> {
+        self.with_retries(|| {
+            self.pubsub
+                .publish_file_upload_verification_outcome(pubsub_topic, verification_outcome)
+        })
+        .await
+    }
+
+    async fn publish_file_upload_completed_event(
+        &self,
+        pubsub_topic: &String,
+        file_upload_completed_event: &FileUploadCompletedEvent,
+    ) -> Result<(), AppError> {
+        self.with_retries(|| {
+            self.pubsub
+                .publish_file_upload_completed_event(pubsub_topic, file_upload_completed_event)
+        })
+        .await
+    }
+
+    async fn publish_upload_aborted_event(
+        &self,
+        pubsub_topic: &String,
+        upload_aborted_event: &UploadAbortedEvent,
+    ) -> Result<(), AppError> {
+        self.with_retries(|| {
+            self.pubsub
+                .publish_upload_aborted_event(pubsub_topic, upload_aborted_event)
+        })
+        .await
+    }
+}
+
+impl RetryingPubSub {
+    async fn with_retries<T, F, Fut>(&self, publish: F) -> Result<T, AppError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            let error = match publish().await {
+                Ok(published) => return Ok(published),
+                Err(error) => error,
+            };
+
+            if attempt >= self.retry_policy.max_attempts || !self.retry_policy.is_retryable(&error)
+            {
+                return Err(error);
+            }
+
+            tokio::time::sleep(self.retry_policy.delay_before_retry(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use mockall::Sequence;
+
+use crate::internal::{
+    interfaces::pubsub_repo::{MockPubSubRepositoryInterface, PubSubRepositoryInterface},
+    models::entities::published_file_upload_chunk::PublishedFileUploadChunk,
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{FileUploadChunk, FileUploadChunkSource},
+        recon_tasks_models::ReconciliationConfigs,
+    },
+};
+
+use super::retrying_pubsub::{RetryingPubSub, RetryPolicy};
+
+#[actix_rt::test]
+async fn given_connection_error_retries_until_publish_succeeds() {
+    let mut mock_pubsub = Box::new(MockPubSubRepositoryInterface::new());
+    let mut sequence = Sequence::new();
+
+    mock_pubsub
+        .expect_save_file_upload_chunk_to_primary_file_queue()
+        .times(2)
+        .in_sequence(&mut sequence)
+        .returning(|_y| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                "unable to connect".to_string(),
+            ))
+        });
+
+    mock_pubsub
+        .expect_save_file_upload_chunk_to_primary_file_queue()
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    let sut = setup_retrying_pubsub(mock_pubsub, 3);
+
+    let actual = sut
+        .save_file_upload_chunk_to_primary_file_queue(&dummy_valid_file_chunk())
+        .await;
+
+    assert_eq!(actual.unwrap(), "FILE_CHUNK_1234");
+}
+
+#[actix_rt::test]
+async fn given_non_retryable_error_returns_error_without_retrying() {
+    let mut mock_pubsub = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub
+        .expect_save_file_upload_chunk_to_primary_file_queue()
+        .times(1)
+        .returning(|_y| {
+            Err(AppError::new(
+                AppErrorKind::InternalError,
+                "status: InvalidArgument, message: \"no such topic\"".to_string(),
+            ))
+        });
+
+    let sut = setup_retrying_pubsub(mock_pubsub, 3);
+
+    let actual = sut
+        .save_file_upload_chunk_to_primary_file_queue(&dummy_valid_file_chunk())
+        .await;
+
+    assert!(actual.is_err());
+}
+
+#[actix_rt::test]
+async fn given_publish_keeps_failing_gives_up_after_max_attempts() {
+    let mut mock_pubsub = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub
+        .expect_save_file_upload_chunk_to_primary_file_queue()
+        .times(3)
+        .returning(|_y| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                "unable to connect".to_string(),
+            ))
+        });
+
+    let sut = setup_retrying_pubsub(mock_pubsub, 3);
+
+    let actual = sut
+        .save_file_upload_chunk_to_primary_file_queue(&dummy_valid_file_chunk())
+        .await;
+
+    assert!(actual.is_err());
+}
+
+#[test]
+fn given_no_jitter_delay_doubles_until_max_delay() {
+    let retry_policy = RetryPolicy {
+        max_attempts: 10,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(500),
+        jitter: 0.0,
+    };
+
+    let actual: Vec<Duration> = (1..=5)
+        .map(|retry_number| retry_policy.delay_before_retry(retry_number))
+        .collect();
+
+    assert_eq!(
+        actual,
+        vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(400),
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+        ]
+    );
+}
+
+#[test]
+fn given_jitter_delay_stays_within_jitter_fraction() {
+    let retry_policy = RetryPolicy {
+        max_attempts: 10,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(500),
+        jitter: 0.5,
+    };
+
+    for _ in 0..100 {
+        let actual = retry_policy.delay_before_retry(2);
+
+        assert!(actual >= Duration::from_millis(100));
+        assert!(actual <= Duration::from_millis(200));
+    }
+}
+
+fn setup_retrying_pubsub(
+    pubsub: Box<dyn PubSubRepositoryInterface>,
+    max_attempts: u32,
+) -> RetryingPubSub {
+    RetryingPubSub {
+        pubsub,
+        retry_policy: RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(0),
+            max_delay: Duration::from_millis(0),
+            jitter: 0.0,
+        },
+    }
+}
+
+fn dummy_valid_file_chunk() -> PublishedFileUploadChunk {
+    let file_upload_chunk = FileUploadChunk {
+        id: String::from("src-file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows: vec![],
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs: vec![],
+        column_headers: vec![],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: true,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: true,
+        },
+        primary_file_chunks_queue: FileChunkQueue {
+            topic_id: String::from("src-file-chunks-queue-1"),
+            last_acknowledged_id: Option::None,
+        },
+        comparison_file_chunks_queue: FileChunkQueue {
+            topic_id: String::from("cmp-file-chunks-queue-1"),
+            last_acknowledged_id: Option::None,
+        },
+        result_chunks_queue: FileChunkQueue {
+            topic_id: String::from("results-file-chunks-queue-1"),
+            last_acknowledged_id: Option::None,
+        },
+        is_last_chunk: false,
+    };
+
+    PublishedFileUploadChunk {
+        file_upload_chunk,
+        normalised_chunk_rows: vec![],
+    }
+}
@@ -0,0 +1,438 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use dapr::dapr::dapr::proto::{
+    common::v1::{state_options::StateConcurrency, Etag, StateItem, StateOptions},
+    runtime::v1::{GetStateRequest, SaveStateRequest},
+};
+use tonic::Code;
+
+use crate::external::{
+    dapr_sidecar::dapr_connection::DaprConnection, pubsub::retrying_pubsub::RetryPolicy,
+};
+use crate::internal::shared_reconciler_rust_libraries::common::utils::app_error;
+use crate::internal::{
+    interfaces::{
+        chunk_ledger_repo::ChunkLedgerRepositoryInterface,
+        upload_status_repo::UploadStatusRepositoryInterface,
+    },
+    models::entities::{
+        chunk_ledger::{ChunkLedger, ChunkLedgerEntry, ChunkReservation, LEDGER_CHUNK_SOURCES},
+        file_upload_status::FileUploadStatus,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_upload_chunk::FileUploadChunkSource,
+    },
+};
+
+//how often, and after how long, a ledger update is retried when another writer got to the
+//ledger first. the jitter keeps the writers that collided from colliding again straight away
+const LEDGER_UPDATE_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 8,
+    base_delay: Duration::from_millis(10),
+    max_delay: Duration::from_millis(500),
+    jitter: 0.5,
+};
+
+/**
+ledgers kept in the dapr state store
+
+every update is a read-modify-write guarded by the etag of the ledger that was read,
+so writers in any number of instances cant overwrite each other's changes. published
+chunks are written once under their own key, so the ledger itself stays small
+ */
+#[derive(Clone)]
+pub struct DaprStateStoreChunkLedger {
+    //the shared channel to the dapr sidecar
+    pub dapr_connection: Arc<DaprConnection>,
+
+    //the dapr state store component name
+    pub dapr_state_store_name: String,
+}
+
+//a ledger as it was read, with the etag to save its next version against
+struct VersionedChunkLedger {
+    chunk_ledger: Option<ChunkLedger>,
+    etag: Option<String>,
+}
+
+#[async_trait]
+impl ChunkLedgerRepositoryInterface for DaprStateStoreChunkLedger {
+    async fn reserve_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        ledger_entry: ChunkLedgerEntry,
+    ) -> Result<ChunkReservation, AppError> {
+        //only a chunk the ledger already counts as published is read back
+        let published_entry = match self
+            .get_chunk_ledger(upload_request_id, chunk_source)
+            .await?
+        {
+            Some(chunk_ledger) if chunk_ledger.is_published(ledger_entry.chunk_sequence_number) => {
+                self.get_published_chunk(
+                    upload_request_id,
+                    chunk_source,
+                    ledger_entry.chunk_sequence_number,
+                )
+                .await?
+            }
+            _ => None,
+        };
+
+        let (_, chunk_reservation) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.reserve(ledger_entry.clone(), published_entry.as_ref())
+            })
+            .await?;
+
+        return Ok(chunk_reservation);
+    }
+
+    async fn confirm_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+    ) -> Result<ChunkLedger, AppError> {
+        //the chunk is saved under its own key before the ledger counts it as published,
+        //so a published chunk can always be read back
+        let reserved_entry = self
+            .get_chunk_ledger(upload_request_id, chunk_source)
+            .await?
+            .and_then(|chunk_ledger| {
+                chunk_ledger
+                    .find_reserved_chunk(chunk_sequence_number)
+                    .cloned()
+            });
+
+        if let Some(reserved_entry) = reserved_entry {
+            self.save_published_chunk(upload_request_id, chunk_source, &reserved_entry)
+                .await?;
+        }
+
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.confirm(chunk_sequence_number)
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn release_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+        file_chunk_id: &String,
+    ) -> Result<ChunkLedger, AppError> {
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.release(chunk_sequence_number, file_chunk_id)
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn claim_completion(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<bool, AppError> {
+        let (_, is_claimed) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.claim_completion()
+            })
+            .await?;
+
+        return Ok(is_claimed);
+    }
+
+    async fn release_completion(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<ChunkLedger, AppError> {
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.release_completion()
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn abort_upload(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<ChunkLedger, AppError> {
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.abort()
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn get_chunk_ledger(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<Option<ChunkLedger>, AppError> {
+        let versioned_chunk_ledger = self
+            .get_versioned_chunk_ledger(upload_request_id, chunk_source)
+            .await?;
+
+        return Ok(versioned_chunk_ledger.chunk_ledger);
+    }
+}
+
+#[async_trait]
+impl UploadStatusRepositoryInterface for DaprStateStoreChunkLedger {
+    async fn get_file_upload_statuses(
+        &self,
+        upload_request_id: &String,
+    ) -> Result<Vec<FileUploadStatus>, AppError> {
+        let mut file_upload_statuses: Vec<FileUploadStatus> = vec![];
+
+        for chunk_source in LEDGER_CHUNK_SOURCES {
+            let chunk_ledger = self
+                .get_chunk_ledger(upload_request_id, chunk_source)
+                .await?;
+
+            if let Some(chunk_ledger) = chunk_ledger {
+                file_upload_statuses.push(FileUploadStatus::from_chunk_ledger(&chunk_ledger));
+            }
+        }
+
+        return Ok(file_upload_statuses);
+    }
+}
+
+impl DaprStateStoreChunkLedger {
+    pub fn new(
+        dapr_connection: Arc<DaprConnection>,
+        dapr_state_store_name: String,
+    ) -> DaprStateStoreChunkLedger {
+        return DaprStateStoreChunkLedger {
+            dapr_connection,
+            dapr_state_store_name,
+        };
+    }
+
+    /**
+    applies the update to the ledger, starting a new one if there is none yet, and saves it
+    against the etag it was read with, reading it again and reapplying the update whenever
+    another writer saved the ledger in between. returns the ledger with what the update returned
+
+    # Errors
+
+    This function will return an error if the state store cant be reached or the ledger
+    kept changing for every attempt of LEDGER_UPDATE_RETRY_POLICY
+     */
+    async fn update_chunk_ledger<T, F>(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        update: F,
+    ) -> Result<(ChunkLedger, T), AppError>
+    where
+        T: Send,
+        F: Fn(&mut ChunkLedger) -> T + Send + Sync,
+    {
+        for attempt in 0..LEDGER_UPDATE_RETRY_POLICY.max_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(LEDGER_UPDATE_RETRY_POLICY.delay_before_retry(attempt)).await;
+            }
+
+            let versioned_chunk_ledger = self
+                .get_versioned_chunk_ledger(upload_request_id, chunk_source)
+                .await?;
+
+            let mut chunk_ledger = match &versioned_chunk_ledger.chunk_ledger {
+                None => ChunkLedger::new(upload_request_id.clone(), chunk_source),
+                Some(chunk_ledger) => chunk_ledger.clone(),
+            };
+
+            let update_result = update(&mut chunk_ledger);
+
+            //nothing changed, so there is nothing to save
+            if versioned_chunk_ledger.chunk_ledger.as_ref() == Some(&chunk_ledger) {
+                return Ok((chunk_ledger, update_result));
+            }
+
+            let is_saved = self
+                .save_chunk_ledger(&chunk_ledger, versioned_chunk_ledger.etag)
+                .await?;
+
+            if is_saved {
+                return Ok((chunk_ledger, update_result));
+            }
+        }
+
+        let error_msg = format!(
+            "the {:?} chunk ledger of upload [{}] kept changing while it was being updated, \
+            please retry",
+            chunk_source, upload_request_id
+        );
+        return Err(AppError::new(AppErrorKind::ConnectionError, error_msg));
+    }
+
+    async fn get_published_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+    ) -> Result<Option<ChunkLedgerEntry>, AppError> {
+        let mut client = self.dapr_connection.get_client().await?;
+
+        let get_state_request = GetStateRequest {
+            store_name: self.dapr_state_store_name.clone(),
+            key: ChunkLedger::chunk_key_for(upload_request_id, chunk_source, chunk_sequence_number),
+            ..Default::default()
+        };
+        let get_state_response = client.get_state(get_state_request).await;
+
+        let state = match get_state_response {
+            Ok(state) => state.into_inner(),
+            Err(e) => {
+                self.dapr_connection.handle_error(&e).await;
+                return app_error(AppErrorKind::ConnectionError, Box::new(e));
+            }
+        };
+
+        if state.data.is_empty() {
+            return Ok(None);
+        }
+
+        return match serde_json::from_slice::<ChunkLedgerEntry>(&state.data) {
+            Ok(ledger_entry) => Ok(Some(ledger_entry)),
+            Err(e) => app_error(AppErrorKind::InternalError, Box::new(e)),
+        };
+    }
+
+    /**
+    saves a published chunk under its own key, the same chunk may be saved more than once
+    if a confirm is retried
+     */
+    async fn save_published_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        ledger_entry: &ChunkLedgerEntry,
+    ) -> Result<(), AppError> {
+        let mut client = self.dapr_connection.get_client().await?;
+
+        let save_state_request = SaveStateRequest {
+            store_name: self.dapr_state_store_name.clone(),
+            states: vec![StateItem {
+                key: ChunkLedger::chunk_key_for(
+                    upload_request_id,
+                    chunk_source,
+                    ledger_entry.chunk_sequence_number,
+                ),
+                value: serde_json::to_vec(ledger_entry).unwrap(),
+                ..Default::default()
+            }],
+        };
+        let save_state_response = client.save_state(save_state_request).await;
+
+        return match save_state_response {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                self.dapr_connection.handle_error(&e).await;
+                app_error(AppErrorKind::ConnectionError, Box::new(e))
+            }
+        };
+    }
+
+    async fn get_versioned_chunk_ledger(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<VersionedChunkLedger, AppError> {
+        let mut client = self.dapr_connection.get_client().await?;
+
+        let get_state_request = GetStateRequest {
+            store_name: self.dapr_state_store_name.clone(),
+            key: ChunkLedger::key_for(upload_request_id, chunk_source),
+            ..Default::default()
+        };
+        let get_state_response = client.get_state(get_state_request).await;
+
+        let state = match get_state_response {
+            Ok(state) => state.into_inner(),
+            Err(e) => {
+                self.dapr_connection.handle_error(&e).await;
+                return app_error(AppErrorKind::ConnectionError, Box::new(e));
+            }
+        };
+
+        //dapr returns no data and no etag for a key that was never saved
+        if state.data.is_empty() {
+            return Ok(VersionedChunkLedger {
+                chunk_ledger: None,
+                etag: None,
+            });
+        }
+
+        return match serde_json::from_slice::<ChunkLedger>(&state.data) {
+            Ok(chunk_ledger) => Ok(VersionedChunkLedger {
+                chunk_ledger: Some(chunk_ledger),
+                etag: Some(state.etag).filter(|etag| !etag.is_empty()),
+            }),
+            Err(e) => app_error(AppErrorKind::InternalError, Box::new(e)),
+        };
+    }
+
+    /**
+    saves the ledger if it is still at the version with this etag, returning false if
+    another writer saved it first
+
+    with first-write concurrency and no etag the save only succeeds if the key
+    does not exist yet, so two new ledgers for the same file cant both be saved
+     */
+    async fn save_chunk_ledger(
+        &self,
+        chunk_ledger: &ChunkLedger,
+        etag: Option<String>,
+    ) -> Result<bool, AppError> {
+        let mut client = self.dapr_connection.get_client().await?;
+
+        let save_state_request = SaveStateRequest {
+            store_name: self.dapr_state_store_name.clone(),
+            states: vec![StateItem {
+                key: ChunkLedger::key_for(
+                    &chunk_ledger.upload_request_id,
+                    chunk_ledger.chunk_source,
+                ),
+                value: serde_json::to_vec(chunk_ledger).unwrap(),
+                etag: etag.map(|etag| Etag { value: etag }),
+                options: Some(StateOptions {
+                    concurrency: StateConcurrency::ConcurrencyFirstWrite as i32,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+        };
+        let save_state_response = client.save_state(save_state_request).await;
+
+        return match save_state_response {
+            Ok(_) => Ok(true),
+
+            //dapr answers an etag mismatch with aborted
+            Err(e) if e.code() == Code::Aborted => Ok(false),
+
+            Err(e) => {
+                self.dapr_connection.handle_error(&e).await;
+                app_error(AppErrorKind::ConnectionError, Box::new(e))
+            }
+        };
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::internal::{
+    interfaces::{
+        chunk_ledger_repo::ChunkLedgerRepositoryInterface,
+        upload_status_repo::UploadStatusRepositoryInterface,
+    },
+    models::entities::{
+        chunk_ledger::{ChunkLedger, ChunkLedgerEntry, ChunkReservation, LEDGER_CHUNK_SOURCES},
+        file_upload_status::FileUploadStatus,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppError, file_upload_chunk::FileUploadChunkSource,
+    },
+};
+
+/**
+keeps chunk ledgers in process memory, only built for the tests
+
+like the state store, published chunks are kept apart from their ledger.
+clones share the same ledgers
+ */
+#[derive(Clone, Default)]
+pub struct InMemoryChunkLedger {
+    chunk_ledgers: Arc<Mutex<HashMap<String, ChunkLedger>>>,
+    published_chunks: Arc<Mutex<HashMap<String, ChunkLedgerEntry>>>,
+}
+
+#[async_trait]
+impl ChunkLedgerRepositoryInterface for InMemoryChunkLedger {
+    async fn reserve_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        ledger_entry: ChunkLedgerEntry,
+    ) -> Result<ChunkReservation, AppError> {
+        let published_entry = self
+            .published_chunks
+            .lock()
+            .await
+            .get(&ChunkLedger::chunk_key_for(
+                upload_request_id,
+                chunk_source,
+                ledger_entry.chunk_sequence_number,
+            ))
+            .cloned();
+
+        let (_, chunk_reservation) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.reserve(ledger_entry, published_entry.as_ref())
+            })
+            .await?;
+
+        return Ok(chunk_reservation);
+    }
+
+    async fn confirm_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+    ) -> Result<ChunkLedger, AppError> {
+        let mut published_chunks = self.published_chunks.lock().await;
+
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                if let Some(ledger_entry) = chunk_ledger.find_reserved_chunk(chunk_sequence_number)
+                {
+                    published_chunks.insert(
+                        ChunkLedger::chunk_key_for(
+                            upload_request_id,
+                            chunk_source,
+                            chunk_sequence_number,
+                        ),
+                        ledger_entry.clone(),
+                    );
+                }
+                chunk_ledger.confirm(chunk_sequence_number)
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn release_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+        file_chunk_id: &String,
+    ) -> Result<ChunkLedger, AppError> {
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.release(chunk_sequence_number, file_chunk_id)
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn claim_completion(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<bool, AppError> {
+        let (_, is_claimed) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.claim_completion()
+            })
+            .await?;
+
+        return Ok(is_claimed);
+    }
+
+    async fn release_completion(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<ChunkLedger, AppError> {
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.release_completion()
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn abort_upload(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<ChunkLedger, AppError> {
+        let (chunk_ledger, _) = self
+            .update_chunk_ledger(upload_request_id, chunk_source, |chunk_ledger| {
+                chunk_ledger.abort()
+            })
+            .await?;
+
+        return Ok(chunk_ledger);
+    }
+
+    async fn get_chunk_ledger(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<Option<ChunkLedger>, AppError> {
+        let chunk_ledgers = self.chunk_ledgers.lock().await;
+
+        let chunk_ledger =
+            chunk_ledgers.get(&ChunkLedger::key_for(upload_request_id, chunk_source));
+
+        return Ok(chunk_ledger.cloned());
+    }
+}
+
+#[async_trait]
+impl UploadStatusRepositoryInterface for InMemoryChunkLedger {
+    async fn get_file_upload_statuses(
+        &self,
+        upload_request_id: &String,
+    ) -> Result<Vec<FileUploadStatus>, AppError> {
+        let mut file_upload_statuses: Vec<FileUploadStatus> = vec![];
+
+        for chunk_source in LEDGER_CHUNK_SOURCES {
+            let chunk_ledger = self
+                .get_chunk_ledger(upload_request_id, chunk_source)
+                .await?;
+
+            if let Some(chunk_ledger) = chunk_ledger {
+                file_upload_statuses.push(FileUploadStatus::from_chunk_ledger(&chunk_ledger));
+            }
+        }
+
+        return Ok(file_upload_statuses);
+    }
+}
+
+impl InMemoryChunkLedger {
+    pub fn new() -> InMemoryChunkLedger {
+        return InMemoryChunkLedger::default();
+    }
+
+    /**
+    applies the update to the ledger, starting a new one if there is none yet,
+    with the ledgers locked throughout, and returns the ledger with what the update returned
+     */
+    async fn update_chunk_ledger<T, F>(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        update: F,
+    ) -> Result<(ChunkLedger, T), AppError>
+    where
+        T: Send,
+        F: FnOnce(&mut ChunkLedger) -> T + Send,
+    {
+        let mut chunk_ledgers = self.chunk_ledgers.lock().await;
+
+        let chunk_ledger = chunk_ledgers
+            .entry(ChunkLedger::key_for(upload_request_id, chunk_source))
+            .or_insert_with(|| ChunkLedger::new(upload_request_id.clone(), chunk_source));
+
+        let update_result = update(chunk_ledger);
+
+        return Ok((chunk_ledger.clone(), update_result));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::internal::{
+    interfaces::chunk_ledger_repo::ChunkLedgerRepositoryInterface,
+    models::entities::chunk_ledger::{ChunkLedger, ChunkLedgerEntry, ChunkReservation},
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource,
+};
+
+use super::in_memory_chunk_ledger::InMemoryChunkLedger;
+
+#[actix_rt::test]
+async fn given_last_chunk_not_received_reports_no_missing_chunks_yet() {
+    let sut = InMemoryChunkLedger::new();
+
+    let actual = record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(3, false),
+    )
+    .await;
+
+    assert_eq!(actual.missing_chunk_sequence_numbers(), None);
+    assert_eq!(actual.published_chunk_count, 1);
+}
+
+#[actix_rt::test]
+async fn given_last_chunk_received_with_gaps_reports_missing_chunks() {
+    let sut = InMemoryChunkLedger::new();
+
+    for ledger_entry in vec![new_ledger_entry(1, false), new_ledger_entry(3, false)] {
+        record_published_chunk(&sut, FileUploadChunkSource::PrimaryFileChunk, ledger_entry).await;
+    }
+
+    let actual = record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(5, true),
+    )
+    .await;
+
+    assert_eq!(actual.missing_chunk_sequence_numbers(), Some(vec![2, 4]));
+}
+
+#[actix_rt::test]
+async fn given_missing_chunk_arrives_after_last_chunk_reports_no_gaps() {
+    let sut = InMemoryChunkLedger::new();
+
+    for ledger_entry in vec![new_ledger_entry(2, true), new_ledger_entry(1, false)] {
+        record_published_chunk(
+            &sut,
+            FileUploadChunkSource::ComparisonFileChunk,
+            ledger_entry,
+        )
+        .await;
+    }
+
+    let actual = sut
+        .get_chunk_ledger(
+            &String::from("TEST-UPLOAD-1"),
+            FileUploadChunkSource::ComparisonFileChunk,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(actual.missing_chunk_sequence_numbers(), Some(vec![]));
+}
+
+#[actix_rt::test]
+async fn given_chunks_for_other_source_keeps_ledgers_apart() {
+    let sut = InMemoryChunkLedger::new();
+
+    record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(1, false),
+    )
+    .await;
+
+    let actual = sut
+        .get_chunk_ledger(
+            &String::from("TEST-UPLOAD-1"),
+            FileUploadChunkSource::ComparisonFileChunk,
+        )
+        .await
+        .unwrap();
+
+    assert!(actual.is_none());
+}
+
+#[actix_rt::test]
+async fn given_last_chunk_carries_file_hash_reports_it_before_the_gaps_are_filled() {
+    let sut = InMemoryChunkLedger::new();
+    let mut last_ledger_entry = new_ledger_entry(2, true);
+    last_ledger_entry.file_hash = Some(String::from("FILE-HASH"));
+
+    let actual = record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        last_ledger_entry,
+    )
+    .await;
+
+    assert!(!actual.is_complete());
+    assert_eq!(actual.file_hash, Some(String::from("FILE-HASH")));
+}
+
+#[actix_rt::test]
+async fn given_reserved_chunk_not_confirmed_it_does_not_count_towards_the_upload() {
+    let sut = InMemoryChunkLedger::new();
+    let upload_request_id = String::from("TEST-UPLOAD-1");
+
+    record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(1, false),
+    )
+    .await;
+
+    sut.reserve_chunk(
+        &upload_request_id,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(2, true),
+    )
+    .await
+    .unwrap();
+
+    let actual = sut
+        .get_chunk_ledger(&upload_request_id, FileUploadChunkSource::PrimaryFileChunk)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!actual.is_complete());
+    assert_eq!(actual.published_chunk_count, 1);
+    assert_eq!(
+        actual.known_missing_chunk_sequence_numbers(),
+        Vec::<i64>::new()
+    );
+    assert!(actual.find_reserved_chunk(2).is_some());
+}
+
+#[actix_rt::test]
+async fn given_reserved_chunk_released_removes_it_from_the_ledger() {
+    let sut = InMemoryChunkLedger::new();
+    let upload_request_id = String::from("TEST-UPLOAD-1");
+
+    sut.reserve_chunk(
+        &upload_request_id,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(1, false),
+    )
+    .await
+    .unwrap();
+
+    let actual = sut
+        .release_chunk(
+            &upload_request_id,
+            FileUploadChunkSource::PrimaryFileChunk,
+            1,
+            &String::from("FILE-CHUNK-1"),
+        )
+        .await
+        .unwrap();
+
+    assert!(actual.find_reserved_chunk(1).is_none());
+}
+
+#[actix_rt::test]
+async fn given_published_chunk_released_keeps_it_in_the_ledger() {
+    let sut = InMemoryChunkLedger::new();
+
+    record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(1, false),
+    )
+    .await;
+
+    let actual = sut
+        .release_chunk(
+            &String::from("TEST-UPLOAD-1"),
+            FileUploadChunkSource::PrimaryFileChunk,
+            1,
+            &String::from("FILE-CHUNK-1"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(actual.published_chunk_count, 1);
+}
+
+#[actix_rt::test]
+async fn given_published_chunk_resent_reads_it_back_to_find_it_already_accepted() {
+    let sut = InMemoryChunkLedger::new();
+
+    record_published_chunk(
+        &sut,
+        FileUploadChunkSource::PrimaryFileChunk,
+        new_ledger_entry(1, false),
+    )
+    .await;
+
+    let mut resent_ledger_entry = new_ledger_entry(1, false);
+    resent_ledger_entry.file_chunk_id = String::from("FILE-CHUNK-RESENT");
+    let actual = sut
+        .reserve_chunk(
+            &String::from("TEST-UPLOAD-1"),
+            FileUploadChunkSource::PrimaryFileChunk,
+            resent_ledger_entry,
+        )
+        .await
+        .unwrap();
+
+    match actual {
+        ChunkReservation::AlreadyAccepted { file_chunk_id, .. } => {
+            assert_eq!(file_chunk_id, "FILE-CHUNK-1")
+        }
+        other => panic!("expected AlreadyAccepted, got {:?}", other),
+    }
+}
+
+async fn record_published_chunk(
+    sut: &InMemoryChunkLedger,
+    chunk_source: FileUploadChunkSource,
+    ledger_entry: ChunkLedgerEntry,
+) -> ChunkLedger {
+    let upload_request_id = String::from("TEST-UPLOAD-1");
+    let chunk_sequence_number = ledger_entry.chunk_sequence_number;
+
+    sut.reserve_chunk(&upload_request_id, chunk_source, ledger_entry)
+        .await
+        .unwrap();
+
+    return sut
+        .confirm_chunk(&upload_request_id, chunk_source, chunk_sequence_number)
+        .await
+        .unwrap();
+}
+
+fn new_ledger_entry(chunk_sequence_number: i64, is_last_chunk: bool) -> ChunkLedgerEntry {
+    ChunkLedgerEntry {
+        chunk_sequence_number,
+        file_chunk_id: format!("FILE-CHUNK-{}", chunk_sequence_number),
+        row_count: 10,
+        failed_row_count: 0,
+        content_hash: format!("HASH-{}", chunk_sequence_number),
+        is_last_chunk,
+        file_hash: None,
+        is_file_hash_computed: false,
+        date_received: chrono::Utc::now().timestamp(),
+    }
+}
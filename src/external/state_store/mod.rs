@@ -0,0 +1,8 @@
+pub mod dapr_state_store_chunk_ledger;
+
+#[cfg(test)]
+pub mod in_memory_chunk_ledger;
+
+#[cfg(test)]
+#[path = "./in_memory_chunk_ledger_tests.rs"]
+mod in_memory_chunk_ledger_tests;
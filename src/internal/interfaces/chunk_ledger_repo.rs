@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::models::entities::chunk_ledger::{
+    ChunkLedger, ChunkLedgerEntry, ChunkReservation,
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::{
+    app_errors::AppError, file_upload_chunk::FileUploadChunkSource,
+};
+
+#[automock]
+#[async_trait]
+pub trait ChunkLedgerRepositoryInterface: Send + Sync {
+    async fn reserve_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        ledger_entry: ChunkLedgerEntry,
+    ) -> Result<ChunkReservation, AppError>;
+
+    async fn confirm_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+    ) -> Result<ChunkLedger, AppError>;
+
+    async fn release_chunk(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+        file_chunk_id: &String,
+    ) -> Result<ChunkLedger, AppError>;
+
+    async fn claim_completion(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<bool, AppError>;
+
+    async fn release_completion(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<ChunkLedger, AppError>;
+
+    async fn abort_upload(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<ChunkLedger, AppError>;
+
+    async fn get_chunk_ledger(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<Option<ChunkLedger>, AppError>;
+}
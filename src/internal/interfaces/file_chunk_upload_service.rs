@@ -1,9 +1,18 @@
 use crate::internal::{
-    models::view_models::{
-        requests::upload_file_chunk_request::UploadFileChunkRequest,
-        responses::upload_file_chunk_response::UploadFileChunkResponse,
+    models::{
+        entities::service_errors::ServiceError,
+        view_models::{
+            requests::upload_file_chunk_request::UploadFileChunkRequest,
+            responses::{
+                abort_upload_response::AbortUploadResponse,
+                upload_file_chunk_response::UploadFileChunkResponse,
+                upload_file_chunks_response::UploadFileChunksResponse,
+                upload_status_response::UploadStatusResponse,
+            },
+        },
     },
-    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+    services::upload_drain::InFlightUpload,
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource,
 };
 use async_trait::async_trait;
 use mockall::automock;
@@ -14,5 +23,31 @@ pub trait FileChunkUploadServiceInterface: Send + Sync {
     async fn upload_file_chunk(
         &self,
         file_upload_chunk: UploadFileChunkRequest,
-    ) -> Result<UploadFileChunkResponse, AppError>;
+    ) -> Result<UploadFileChunkResponse, ServiceError>;
+
+    /**
+    uploads a chunk split off a file whose upload is already in flight, the file upload's
+    handle being what lets the chunk in once draining has started
+     */
+    async fn upload_file_chunk_of_file<'a>(
+        &self,
+        file_upload_chunk: UploadFileChunkRequest,
+        in_flight_file: &InFlightUpload<'a>,
+    ) -> Result<UploadFileChunkResponse, ServiceError>;
+
+    async fn upload_file_chunks(
+        &self,
+        file_upload_chunks: Vec<UploadFileChunkRequest>,
+    ) -> Result<UploadFileChunksResponse, ServiceError>;
+
+    async fn get_upload_status(
+        &self,
+        upload_request_id: String,
+    ) -> Result<UploadStatusResponse, ServiceError>;
+
+    async fn abort_upload(
+        &self,
+        upload_request_id: String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<AbortUploadResponse, ServiceError>;
 }
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::models::view_models::responses::health_response::HealthResponse;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
+
+#[automock]
+#[async_trait]
+pub trait DependencyHealthCheckInterface: Send + Sync {
+    //how the dependency is named in the readiness report
+    fn dependency_name(&self) -> String;
+
+    async fn check_health(&self) -> Result<(), AppError>;
+}
+
+#[automock]
+#[async_trait]
+pub trait HealthServiceInterface: Send + Sync {
+    async fn check_readiness(&self) -> HealthResponse;
+}
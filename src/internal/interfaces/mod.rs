@@ -1,4 +1,7 @@
+pub mod chunk_ledger_repo;
 pub mod file_chunk_upload_service;
+pub mod health_check;
 pub mod pubsub_repo;
 pub mod recon_tasks_repo;
 pub mod transformer;
+pub mod upload_status_repo;
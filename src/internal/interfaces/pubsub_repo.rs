@@ -1,20 +1,42 @@
 use async_trait::async_trait;
 use mockall::automock;
 
-use crate::internal::shared_reconciler_rust_libraries::models::entities::{
-    app_errors::AppError, file_upload_chunk::FileUploadChunk,
+use crate::internal::models::entities::{
+    file_upload_completed_event::FileUploadCompletedEvent,
+    file_upload_verification::FileUploadVerificationOutcome,
+    published_file_upload_chunk::PublishedFileUploadChunk,
+    upload_aborted_event::UploadAbortedEvent,
 };
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
 
 #[automock]
 #[async_trait]
 pub trait PubSubRepositoryInterface: Send + Sync {
     async fn save_file_upload_chunk_to_primary_file_queue(
         &self,
-        file_upload_chunk: &FileUploadChunk,
+        published_file_chunk: &PublishedFileUploadChunk,
     ) -> Result<String, AppError>;
 
     async fn save_file_upload_chunk_to_comparison_file_queue(
         &self,
-        file_upload_chunk: &FileUploadChunk,
+        published_file_chunk: &PublishedFileUploadChunk,
     ) -> Result<String, AppError>;
+
+    async fn publish_file_upload_verification_outcome(
+        &self,
+        pubsub_topic: &String,
+        verification_outcome: &FileUploadVerificationOutcome,
+    ) -> Result<(), AppError>;
+
+    async fn publish_file_upload_completed_event(
+        &self,
+        pubsub_topic: &String,
+        file_upload_completed_event: &FileUploadCompletedEvent,
+    ) -> Result<(), AppError>;
+
+    async fn publish_upload_aborted_event(
+        &self,
+        pubsub_topic: &String,
+        upload_aborted_event: &UploadAbortedEvent,
+    ) -> Result<(), AppError>;
 }
@@ -13,4 +13,9 @@ pub trait ReconTasksDetailsRetrieverInterface: Send + Sync {
         &self,
         task_id: &String,
     ) -> Result<ReconTaskResponseDetails, AppError>;
+
+    /**
+    forgets anything held about the task, so the next lookup sees its latest state
+     */
+    async fn invalidate_recon_task_details(&self, task_id: &String);
 }
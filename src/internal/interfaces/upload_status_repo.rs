@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::models::entities::file_upload_status::FileUploadStatus;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
+
+#[automock]
+#[async_trait]
+pub trait UploadStatusRepositoryInterface: Send + Sync {
+    async fn get_file_upload_statuses(
+        &self,
+        upload_request_id: &String,
+    ) -> Result<Vec<FileUploadStatus>, AppError>;
+}
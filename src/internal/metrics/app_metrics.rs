@@ -0,0 +1,125 @@
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry, Encoder,
+    HistogramVec, IntCounterVec, Registry, TextEncoder,
+};
+
+use crate::internal::models::entities::service_errors::ServiceError;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::{
+    app_errors::{AppError, AppErrorKind},
+    file_upload_chunk::FileUploadChunkSource,
+};
+
+const SUCCESS_OUTCOME: &'static str = "success";
+const ERROR_OUTCOME: &'static str = "error";
+
+//labels only ever take a handful of values, ids of tasks, uploads or chunks never become labels
+lazy_static! {
+    pub static ref REGISTRY: Registry = Registry::new();
+    pub static ref FILE_CHUNKS_RECEIVED: IntCounterVec = register_int_counter_vec_with_registry!(
+        "file_chunks_received_total",
+        "file chunks received, before any validation",
+        &["chunk_source"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref FILE_CHUNKS_PUBLISHED: IntCounterVec = register_int_counter_vec_with_registry!(
+        "file_chunks_published_total",
+        "file chunks published to their chunks queue",
+        &["chunk_source"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref ROWS_PARSED: IntCounterVec = register_int_counter_vec_with_registry!(
+        "file_rows_parsed_total",
+        "rows of file chunks parsed into columns, failed rows included",
+        &["chunk_source"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref ROWS_FAILED: IntCounterVec = register_int_counter_vec_with_registry!(
+        "file_rows_failed_total",
+        "rows of file chunks marked as failed while being parsed",
+        &["chunk_source"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref VALIDATION_REJECTIONS: IntCounterVec = register_int_counter_vec_with_registry!(
+        "validation_rejections_total",
+        "requests rejected by validation, once for every field at fault",
+        &["field"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref PUBSUB_PUBLISH_DURATION: HistogramVec = register_histogram_vec_with_registry!(
+        "pubsub_publish_duration_seconds",
+        "time taken to publish an event to the dapr pubsub",
+        &["event_type", "outcome"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref PUBSUB_PUBLISH_ERRORS: IntCounterVec = register_int_counter_vec_with_registry!(
+        "pubsub_publish_errors_total",
+        "events that failed to be published to the dapr pubsub",
+        &["event_type", "error_kind"],
+        REGISTRY
+    )
+    .unwrap();
+    pub static ref RECON_TASK_LOOKUP_DURATION: HistogramVec =
+        register_histogram_vec_with_registry!(
+            "recon_task_lookup_duration_seconds",
+            "time taken to look up the details of a recon task",
+            &["outcome"],
+            REGISTRY
+        )
+        .unwrap();
+}
+
+pub fn chunk_source_label(chunk_source: FileUploadChunkSource) -> &'static str {
+    return match chunk_source {
+        FileUploadChunkSource::PrimaryFileChunk => "primary",
+        FileUploadChunkSource::ComparisonFileChunk => "comparison",
+    };
+}
+
+pub fn error_kind_label(app_error_kind: &AppErrorKind) -> String {
+    return format!("{:?}", app_error_kind);
+}
+
+pub fn outcome_label<T, E>(result: &Result<T, E>) -> &'static str {
+    return match result {
+        Ok(_) => SUCCESS_OUTCOME,
+        Err(_) => ERROR_OUTCOME,
+    };
+}
+
+/**
+counts a validation failure against each of the fields that caused it
+ */
+pub fn record_validation_rejection(service_error: &ServiceError) {
+    for invalid_field in &service_error.invalid_fields {
+        VALIDATION_REJECTIONS
+            .with_label_values(&[invalid_field.as_str()])
+            .inc();
+    }
+}
+
+/**
+every metric in the prometheus text format
+
+# Errors
+
+This function will return an error if the metrics cant be encoded
+ */
+pub fn gather_metrics() -> Result<String, AppError> {
+    let mut encoded_metrics: Vec<u8> = vec![];
+
+    if let Err(e) = TextEncoder::new().encode(&REGISTRY.gather(), &mut encoded_metrics) {
+        return Err(AppError::new(AppErrorKind::InternalError, e.to_string()));
+    }
+
+    return match String::from_utf8(encoded_metrics) {
+        Ok(encoded_metrics) => Ok(encoded_metrics),
+        Err(e) => Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+    };
+}
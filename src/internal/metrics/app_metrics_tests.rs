@@ -0,0 +1,31 @@
+use super::app_metrics::{
+    chunk_source_label, gather_metrics, record_validation_rejection, FILE_CHUNKS_RECEIVED,
+};
+use crate::internal::models::entities::service_errors::{ServiceError, ServiceErrorKind};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+#[test]
+fn test_gather_metrics_exports_counters_labelled_by_chunk_source() {
+    FILE_CHUNKS_RECEIVED
+        .with_label_values(&[chunk_source_label(FileUploadChunkSource::PrimaryFileChunk)])
+        .inc();
+
+    let actual = gather_metrics().unwrap();
+
+    assert!(actual.contains("file_chunks_received_total{chunk_source=\"primary\"}"));
+}
+
+#[test]
+fn test_record_validation_rejection_counts_each_invalid_field() {
+    let validation_error = ServiceError {
+        invalid_fields: vec![String::from("chunk_size"), String::from("upload_request_id")],
+        ..ServiceError::new(ServiceErrorKind::BadClientRequest, String::from("bad request"))
+    };
+
+    record_validation_rejection(&validation_error);
+
+    let actual = gather_metrics().unwrap();
+
+    assert!(actual.contains("validation_rejections_total{field=\"chunk_size\"}"));
+    assert!(actual.contains("validation_rejections_total{field=\"upload_request_id\"}"));
+}
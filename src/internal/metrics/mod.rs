@@ -0,0 +1,5 @@
+pub mod app_metrics;
+
+#[cfg(test)]
+#[path = "./app_metrics_tests.rs"]
+mod app_metrics_tests;
@@ -0,0 +1,490 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+const CHUNK_LEDGER_KEY_PREFIX: &'static str = "CHUNK-LEDGER";
+const LEDGER_CHUNK_KEY_PREFIX: &'static str = "LEDGER-CHUNK";
+
+//responses list at most this many missing chunks, the client sends those and asks again
+pub const MAX_REPORTED_MISSING_CHUNKS: usize = 100;
+
+//a reservation this old belongs to an attempt that is not coming back, publishing a chunk
+//including its retries takes seconds
+const CHUNK_RESERVATION_LEASE_SECONDS: i64 = 60;
+
+//an upload request has at most one ledger for each of these
+pub const LEDGER_CHUNK_SOURCES: [FileUploadChunkSource; 2] = [
+    FileUploadChunkSource::PrimaryFileChunk,
+    FileUploadChunkSource::ComparisonFileChunk,
+];
+
+/**
+what has been accepted so far for one file (primary or comparison) of an upload request
+
+a chunk is reserved in the ledger before it is published and confirmed once it has been,
+so a chunk is never published without the ledger knowing about it. a confirmed chunk only
+leaves its sequence number and counts behind here, the chunk itself is kept under its own
+key (see chunk_key_for) so the ledger stays small however many chunks the file has
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChunkLedger {
+    pub upload_request_id: String,
+    pub chunk_source: FileUploadChunkSource,
+
+    //chunks that are being published right now
+    pub reserved_chunks: Vec<ChunkLedgerEntry>,
+
+    //the sequence numbers of the published chunks, as sorted runs that dont touch
+    pub published_chunk_ranges: Vec<ChunkSequenceRange>,
+
+    pub published_chunk_count: u64,
+    pub published_row_count: u64,
+    pub published_failed_row_count: u64,
+    pub last_chunk_sequence_number: Option<i64>,
+
+    //the hash of the whole file sent with the last chunk, and whether this service
+    //computed it from the file bytes or took the client's word for it
+    pub file_hash: Option<String>,
+    pub is_file_hash_computed: bool,
+
+    //an aborted upload accepts no more chunks
+    pub is_aborted: bool,
+
+    //set by the one request that gets to publish the completion events of the upload
+    pub completion_published: bool,
+
+    pub date_created: i64,
+    pub date_modified: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChunkLedgerEntry {
+    pub chunk_sequence_number: i64,
+    pub file_chunk_id: String,
+    pub row_count: u64,
+    pub failed_row_count: u64,
+    pub content_hash: String,
+    pub is_last_chunk: bool,
+
+    //the hash of the whole file sent with the last chunk
+    pub file_hash: Option<String>,
+    pub is_file_hash_computed: bool,
+
+    pub date_received: i64,
+}
+
+/**
+the chunks first to last, both included, have all been published
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ChunkSequenceRange {
+    pub first: i64,
+    pub last: i64,
+}
+
+/**
+what became of an attempt to reserve a chunk in the ledger
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChunkReservation {
+    //the chunk is to be published under this file chunk id
+    Reserved { file_chunk_id: String },
+
+    //the same chunk was already published under this file chunk id
+    AlreadyAccepted {
+        file_chunk_id: String,
+        chunk_ledger: ChunkLedger,
+    },
+
+    //the same chunk is being published by another request right now
+    InProgress,
+
+    //a different chunk was already accepted under the same sequence number,
+    //or the chunk does not fit with the last chunk of the file
+    Conflict,
+
+    //the upload was aborted and takes no more chunks
+    UploadAborted,
+}
+
+impl ChunkLedger {
+    pub fn new(upload_request_id: String, chunk_source: FileUploadChunkSource) -> ChunkLedger {
+        return ChunkLedger {
+            upload_request_id,
+            chunk_source,
+            reserved_chunks: vec![],
+            published_chunk_ranges: vec![],
+            published_chunk_count: 0,
+            published_row_count: 0,
+            published_failed_row_count: 0,
+            last_chunk_sequence_number: None,
+            file_hash: None,
+            is_file_hash_computed: false,
+            is_aborted: false,
+            completion_published: false,
+            date_created: chrono::Utc::now().timestamp(),
+            date_modified: chrono::Utc::now().timestamp(),
+        };
+    }
+
+    /**
+    the key a ledger is stored under, one per file of an upload request
+     */
+    pub fn key_for(upload_request_id: &String, chunk_source: FileUploadChunkSource) -> String {
+        return format!(
+            "{}-{:?}-{}",
+            CHUNK_LEDGER_KEY_PREFIX, chunk_source, upload_request_id
+        );
+    }
+
+    /**
+    the key a published chunk of the ledger is stored under
+     */
+    pub fn chunk_key_for(
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+        chunk_sequence_number: i64,
+    ) -> String {
+        return format!(
+            "{}-{:?}-{}-{}",
+            LEDGER_CHUNK_KEY_PREFIX, chunk_source, upload_request_id, chunk_sequence_number
+        );
+    }
+
+    /**
+    reserves the chunk ahead of publishing it, unless the same or a different chunk already
+    holds its sequence number or it does not fit with the last chunk of the file; it does not
+    count towards the upload until it is confirmed
+
+    published_entry is the chunk published under the same sequence number, if there is one,
+    which the ledger only knows by its sequence number
+
+    a reservation left behind by an attempt that never came back is taken over, under the same
+    file chunk id if the content is the same, since that attempt may have got as far as publishing
+     */
+    pub fn reserve(
+        &mut self,
+        mut ledger_entry: ChunkLedgerEntry,
+        published_entry: Option<&ChunkLedgerEntry>,
+    ) -> ChunkReservation {
+        if self.is_aborted {
+            return ChunkReservation::UploadAborted;
+        }
+
+        let chunk_sequence_number = ledger_entry.chunk_sequence_number;
+
+        //nothing comes after the last chunk, and a file has only one
+        if let Some(last_chunk_sequence_number) = self.known_last_chunk_sequence_number() {
+            if chunk_sequence_number > last_chunk_sequence_number
+                || (ledger_entry.is_last_chunk
+                    && chunk_sequence_number != last_chunk_sequence_number)
+            {
+                return ChunkReservation::Conflict;
+            }
+        }
+
+        if ledger_entry.is_last_chunk
+            && self.highest_known_chunk_sequence_number() > Some(chunk_sequence_number)
+        {
+            return ChunkReservation::Conflict;
+        }
+
+        match self.find_reserved_chunk(chunk_sequence_number) {
+            None => (),
+            Some(existing_entry)
+                if is_abandoned_reservation(existing_entry)
+                    && existing_entry.content_hash == ledger_entry.content_hash =>
+            {
+                ledger_entry.file_chunk_id = existing_entry.file_chunk_id.clone();
+            }
+            Some(existing_entry) if is_abandoned_reservation(existing_entry) => (),
+            Some(existing_entry) if existing_entry.content_hash != ledger_entry.content_hash => {
+                return ChunkReservation::Conflict;
+            }
+            Some(_) => return ChunkReservation::InProgress,
+        }
+
+        if self.is_published(chunk_sequence_number) {
+            return match published_entry {
+                //the chunk is confirmed but not yet readable under its own key
+                None => ChunkReservation::InProgress,
+                Some(published_entry)
+                    if published_entry.content_hash != ledger_entry.content_hash =>
+                {
+                    ChunkReservation::Conflict
+                }
+                Some(published_entry) => ChunkReservation::AlreadyAccepted {
+                    file_chunk_id: published_entry.file_chunk_id.clone(),
+                    chunk_ledger: self.clone(),
+                },
+            };
+        }
+
+        let file_chunk_id = ledger_entry.file_chunk_id.clone();
+        ledger_entry.date_received = chrono::Utc::now().timestamp();
+
+        self.reserved_chunks
+            .retain(|entry| entry.chunk_sequence_number != chunk_sequence_number);
+        self.reserved_chunks.push(ledger_entry);
+
+        self.date_modified = chrono::Utc::now().timestamp();
+
+        return ChunkReservation::Reserved { file_chunk_id };
+    }
+
+    /**
+    counts a reserved chunk as published, it is then only kept under its own key
+     */
+    pub fn confirm(&mut self, chunk_sequence_number: i64) {
+        let position = match self
+            .reserved_chunks
+            .iter()
+            .position(|entry| entry.chunk_sequence_number == chunk_sequence_number)
+        {
+            None => return,
+            Some(position) => position,
+        };
+        let ledger_entry = self.reserved_chunks.remove(position);
+
+        if !self.is_published(chunk_sequence_number) {
+            self.add_published_chunk_sequence_number(chunk_sequence_number);
+            self.published_chunk_count += 1;
+            self.published_row_count += ledger_entry.row_count;
+            self.published_failed_row_count += ledger_entry.failed_row_count;
+        }
+
+        if ledger_entry.is_last_chunk {
+            self.last_chunk_sequence_number = Some(chunk_sequence_number);
+            self.file_hash = ledger_entry.file_hash;
+            self.is_file_hash_computed = ledger_entry.is_file_hash_computed;
+        }
+
+        self.date_modified = chrono::Utc::now().timestamp();
+    }
+
+    /**
+    drops a reserved chunk whose publish failed, so that it can be sent again
+     */
+    pub fn release(&mut self, chunk_sequence_number: i64, file_chunk_id: &String) {
+        let chunk_count = self.reserved_chunks.len();
+
+        self.reserved_chunks.retain(|entry| {
+            entry.chunk_sequence_number != chunk_sequence_number
+                || entry.file_chunk_id != *file_chunk_id
+        });
+
+        if self.reserved_chunks.len() != chunk_count {
+            self.date_modified = chrono::Utc::now().timestamp();
+        }
+    }
+
+    /**
+    claims the publishing of the completion events for the caller, true only for the first
+    caller once the upload is complete
+     */
+    pub fn claim_completion(&mut self) -> bool {
+        if !self.is_complete() || self.is_aborted || self.completion_published {
+            return false;
+        }
+
+        self.completion_published = true;
+        return true;
+    }
+
+    /**
+    gives up a claim whose completion events failed to be published, so they can be tried again
+     */
+    pub fn release_completion(&mut self) {
+        self.completion_published = false;
+    }
+
+    /**
+    marks the upload aborted, unless it is already complete and so handed over to the reconciler
+     */
+    pub fn abort(&mut self) {
+        if self.is_aborted || self.is_complete() {
+            return;
+        }
+
+        self.is_aborted = true;
+        self.date_modified = chrono::Utc::now().timestamp();
+    }
+
+    pub fn find_reserved_chunk(&self, chunk_sequence_number: i64) -> Option<&ChunkLedgerEntry> {
+        return self
+            .reserved_chunks
+            .iter()
+            .find(|entry| entry.chunk_sequence_number == chunk_sequence_number);
+    }
+
+    pub fn is_published(&self, chunk_sequence_number: i64) -> bool {
+        return self
+            .published_chunk_ranges
+            .binary_search_by(|range| {
+                if range.last < chunk_sequence_number {
+                    Ordering::Less
+                } else if range.first > chunk_sequence_number {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok();
+    }
+
+    pub fn total_row_count(&self) -> u64 {
+        return self.published_row_count;
+    }
+
+    pub fn total_failed_row_count(&self) -> u64 {
+        return self.published_failed_row_count;
+    }
+
+    /**
+    true once the last chunk and every chunk before it has been published
+     */
+    pub fn is_complete(&self) -> bool {
+        return match self.last_chunk_sequence_number {
+            None => false,
+            Some(last_chunk_sequence_number) => {
+                self.published_chunk_ranges
+                    == [ChunkSequenceRange {
+                        first: 1,
+                        last: last_chunk_sequence_number,
+                    }]
+            }
+        };
+    }
+
+    /**
+    the first MAX_REPORTED_MISSING_CHUNKS sequence numbers between 1 and the last chunk
+    that have not been published, or None while the last chunk is still to come
+     */
+    pub fn missing_chunk_sequence_numbers(&self) -> Option<Vec<i64>> {
+        let last_chunk_sequence_number = self.last_chunk_sequence_number?;
+
+        return Some(self.missing_chunk_sequence_numbers_up_to(last_chunk_sequence_number));
+    }
+
+    /**
+    the first MAX_REPORTED_MISSING_CHUNKS sequence numbers that have not been published
+    between 1 and the last chunk, or the highest chunk published so far while the last chunk
+    is still to come
+     */
+    pub fn known_missing_chunk_sequence_numbers(&self) -> Vec<i64> {
+        return self.missing_chunk_sequence_numbers_up_to(self.known_upper_chunk_sequence_number());
+    }
+
+    /**
+    how many sequence numbers known_missing_chunk_sequence_numbers would list without its cap
+     */
+    pub fn known_missing_chunk_count(&self) -> u64 {
+        let upper_chunk_sequence_number = self.known_upper_chunk_sequence_number().max(0) as u64;
+
+        return upper_chunk_sequence_number.saturating_sub(self.published_chunk_count);
+    }
+
+    pub fn highest_chunk_sequence_number(&self) -> Option<i64> {
+        return self.published_chunk_ranges.last().map(|range| range.last);
+    }
+
+    fn known_upper_chunk_sequence_number(&self) -> i64 {
+        return match self.last_chunk_sequence_number {
+            Some(last_chunk_sequence_number) => last_chunk_sequence_number,
+            None => self.highest_chunk_sequence_number().unwrap_or(0),
+        };
+    }
+
+    //the last chunk as far as anyone knows, whether it is published or still being published
+    fn known_last_chunk_sequence_number(&self) -> Option<i64> {
+        if self.last_chunk_sequence_number.is_some() {
+            return self.last_chunk_sequence_number;
+        }
+
+        return self
+            .reserved_chunks
+            .iter()
+            .find(|entry| entry.is_last_chunk && !is_abandoned_reservation(entry))
+            .map(|entry| entry.chunk_sequence_number);
+    }
+
+    fn highest_known_chunk_sequence_number(&self) -> Option<i64> {
+        let highest_reserved_chunk_sequence_number = self
+            .reserved_chunks
+            .iter()
+            .filter(|entry| !is_abandoned_reservation(entry))
+            .map(|entry| entry.chunk_sequence_number)
+            .max();
+
+        return self
+            .highest_chunk_sequence_number()
+            .max(highest_reserved_chunk_sequence_number);
+    }
+
+    //walks the gaps between the published runs instead of every sequence number
+    fn missing_chunk_sequence_numbers_up_to(&self, upper_chunk_sequence_number: i64) -> Vec<i64> {
+        let mut missing_chunk_sequence_numbers: Vec<i64> = vec![];
+        let mut gap_first = 1;
+
+        //every run ends the gap before it, the end of the file ends the last gap
+        let gap_ends = self
+            .published_chunk_ranges
+            .iter()
+            .map(|range| (range.first, range.last + 1))
+            .chain(std::iter::once((
+                upper_chunk_sequence_number + 1,
+                upper_chunk_sequence_number + 1,
+            )));
+
+        for (gap_end, next_gap_first) in gap_ends {
+            let gap = gap_first..gap_end.min(upper_chunk_sequence_number + 1);
+            let room_left = MAX_REPORTED_MISSING_CHUNKS - missing_chunk_sequence_numbers.len();
+            missing_chunk_sequence_numbers.extend(gap.take(room_left));
+
+            if missing_chunk_sequence_numbers.len() >= MAX_REPORTED_MISSING_CHUNKS
+                || gap_end > upper_chunk_sequence_number
+            {
+                break;
+            }
+            gap_first = next_gap_first;
+        }
+
+        return missing_chunk_sequence_numbers;
+    }
+
+    fn add_published_chunk_sequence_number(&mut self, chunk_sequence_number: i64) {
+        //the first run that ends after the chunk, the chunk goes in before it or joins it
+        let position = self
+            .published_chunk_ranges
+            .partition_point(|range| range.last < chunk_sequence_number);
+
+        let joins_previous_range = position > 0
+            && self.published_chunk_ranges[position - 1].last + 1 == chunk_sequence_number;
+        let joins_next_range = position < self.published_chunk_ranges.len()
+            && self.published_chunk_ranges[position].first - 1 == chunk_sequence_number;
+
+        match (joins_previous_range, joins_next_range) {
+            (true, true) => {
+                let next_range = self.published_chunk_ranges.remove(position);
+                self.published_chunk_ranges[position - 1].last = next_range.last;
+            }
+            (true, false) => self.published_chunk_ranges[position - 1].last = chunk_sequence_number,
+            (false, true) => self.published_chunk_ranges[position].first = chunk_sequence_number,
+            (false, false) => self.published_chunk_ranges.insert(
+                position,
+                ChunkSequenceRange {
+                    first: chunk_sequence_number,
+                    last: chunk_sequence_number,
+                },
+            ),
+        }
+    }
+}
+
+fn is_abandoned_reservation(ledger_entry: &ChunkLedgerEntry) -> bool {
+    let reservation_age_seconds = chrono::Utc::now().timestamp() - ledger_entry.date_received;
+
+    return reservation_age_seconds >= CHUNK_RESERVATION_LEASE_SECONDS;
+}
@@ -0,0 +1,260 @@
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+use super::chunk_ledger::{
+    ChunkLedger, ChunkLedgerEntry, ChunkReservation, ChunkSequenceRange,
+    MAX_REPORTED_MISSING_CHUNKS,
+};
+
+#[test]
+fn test_reserve_new_chunk_reserves_it_under_its_own_file_chunk_id() {
+    let mut chunk_ledger = new_chunk_ledger();
+
+    let actual = chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-1", "HASH-1"), None);
+
+    assert_eq!(
+        actual,
+        ChunkReservation::Reserved {
+            file_chunk_id: String::from("FILE-CHUNK-1")
+        }
+    );
+    assert!(chunk_ledger.find_reserved_chunk(1).is_some());
+    assert!(!chunk_ledger.is_published(1));
+}
+
+#[test]
+fn test_reserve_published_chunk_with_same_content_is_already_accepted() {
+    let mut chunk_ledger = new_chunk_ledger();
+    let published_entry = new_ledger_entry("FILE-CHUNK-1", "HASH-1");
+    chunk_ledger.reserve(published_entry.clone(), None);
+    chunk_ledger.confirm(1);
+
+    let actual = chunk_ledger.reserve(
+        new_ledger_entry("FILE-CHUNK-2", "HASH-1"),
+        Some(&published_entry),
+    );
+
+    match actual {
+        ChunkReservation::AlreadyAccepted { file_chunk_id, .. } => {
+            assert_eq!(file_chunk_id, "FILE-CHUNK-1")
+        }
+        other => panic!("expected AlreadyAccepted, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_reserve_chunk_being_published_is_in_progress() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-1", "HASH-1"), None);
+
+    let actual = chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-2", "HASH-1"), None);
+
+    assert_eq!(actual, ChunkReservation::InProgress);
+}
+
+#[test]
+fn test_reserve_chunk_with_different_content_is_a_conflict() {
+    let mut chunk_ledger = new_chunk_ledger();
+    let published_entry = new_ledger_entry("FILE-CHUNK-1", "HASH-1");
+    chunk_ledger.reserve(published_entry.clone(), None);
+    chunk_ledger.confirm(1);
+
+    let actual = chunk_ledger.reserve(
+        new_ledger_entry("FILE-CHUNK-2", "HASH-2"),
+        Some(&published_entry),
+    );
+
+    assert_eq!(actual, ChunkReservation::Conflict);
+}
+
+#[test]
+fn test_reserve_published_chunk_not_yet_readable_is_in_progress() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-1", "HASH-1"), None);
+    chunk_ledger.confirm(1);
+
+    let actual = chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-2", "HASH-1"), None);
+
+    assert_eq!(actual, ChunkReservation::InProgress);
+}
+
+#[test]
+fn test_reserve_chunk_after_the_last_chunk_is_a_conflict() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_sequenced_ledger_entry(3, true), None);
+    chunk_ledger.confirm(3);
+
+    let actual = chunk_ledger.reserve(new_sequenced_ledger_entry(4, false), None);
+
+    assert_eq!(actual, ChunkReservation::Conflict);
+    assert!(chunk_ledger.find_reserved_chunk(4).is_none());
+}
+
+#[test]
+fn test_reserve_second_last_chunk_with_another_sequence_number_is_a_conflict() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_sequenced_ledger_entry(3, true), None);
+
+    let while_first_is_reserved = chunk_ledger.reserve(new_sequenced_ledger_entry(2, true), None);
+    chunk_ledger.confirm(3);
+    let once_first_is_published = chunk_ledger.reserve(new_sequenced_ledger_entry(2, true), None);
+
+    assert_eq!(while_first_is_reserved, ChunkReservation::Conflict);
+    assert_eq!(once_first_is_published, ChunkReservation::Conflict);
+    assert_eq!(chunk_ledger.last_chunk_sequence_number, Some(3));
+}
+
+#[test]
+fn test_reserve_last_chunk_below_a_chunk_already_received_is_a_conflict() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_sequenced_ledger_entry(5, false), None);
+    chunk_ledger.confirm(5);
+
+    let actual = chunk_ledger.reserve(new_sequenced_ledger_entry(4, true), None);
+
+    assert_eq!(actual, ChunkReservation::Conflict);
+}
+
+#[test]
+fn test_reserve_over_abandoned_reservation_takes_it_over_under_the_same_file_chunk_id() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-1", "HASH-1"), None);
+    chunk_ledger.reserved_chunks[0].date_received -= 3600;
+
+    let actual = chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-2", "HASH-1"), None);
+
+    assert_eq!(
+        actual,
+        ChunkReservation::Reserved {
+            file_chunk_id: String::from("FILE-CHUNK-1")
+        }
+    );
+    assert_eq!(chunk_ledger.reserved_chunks.len(), 1);
+}
+
+#[test]
+fn test_reserve_chunk_of_aborted_upload_is_upload_aborted() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.abort();
+
+    let actual = chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-1", "HASH-1"), None);
+
+    assert_eq!(actual, ChunkReservation::UploadAborted);
+    assert!(chunk_ledger.reserved_chunks.is_empty());
+}
+
+#[test]
+fn test_abort_complete_upload_leaves_it_as_it_is() {
+    let mut chunk_ledger = new_chunk_ledger();
+    let mut last_ledger_entry = new_ledger_entry("FILE-CHUNK-1", "HASH-1");
+    last_ledger_entry.is_last_chunk = true;
+    chunk_ledger.reserve(last_ledger_entry, None);
+    chunk_ledger.confirm(1);
+
+    chunk_ledger.abort();
+
+    assert!(chunk_ledger.is_complete());
+    assert!(!chunk_ledger.is_aborted);
+}
+
+#[test]
+fn test_claim_completion_is_only_granted_once_until_released() {
+    let mut chunk_ledger = new_chunk_ledger();
+    let mut last_ledger_entry = new_ledger_entry("FILE-CHUNK-1", "HASH-1");
+    last_ledger_entry.is_last_chunk = true;
+    chunk_ledger.reserve(last_ledger_entry, None);
+    chunk_ledger.confirm(1);
+
+    let first_claim = chunk_ledger.claim_completion();
+    let second_claim = chunk_ledger.claim_completion();
+    chunk_ledger.release_completion();
+    let claim_after_release = chunk_ledger.claim_completion();
+
+    assert!(first_claim);
+    assert!(!second_claim);
+    assert!(claim_after_release);
+}
+
+#[test]
+fn test_claim_completion_of_incomplete_upload_is_refused() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_ledger_entry("FILE-CHUNK-1", "HASH-1"), None);
+    chunk_ledger.confirm(1);
+
+    let actual = chunk_ledger.claim_completion();
+
+    assert!(!actual);
+    assert!(!chunk_ledger.completion_published);
+}
+
+#[test]
+fn test_confirm_keeps_published_chunks_as_merged_runs() {
+    let mut chunk_ledger = new_chunk_ledger();
+
+    for chunk_sequence_number in [1, 2, 5, 4, 7] {
+        chunk_ledger.reserve(
+            new_sequenced_ledger_entry(chunk_sequence_number, false),
+            None,
+        );
+        chunk_ledger.confirm(chunk_sequence_number);
+    }
+
+    assert_eq!(
+        chunk_ledger.published_chunk_ranges,
+        vec![
+            ChunkSequenceRange { first: 1, last: 2 },
+            ChunkSequenceRange { first: 4, last: 5 },
+            ChunkSequenceRange { first: 7, last: 7 },
+        ]
+    );
+    assert_eq!(chunk_ledger.published_chunk_count, 5);
+    assert_eq!(chunk_ledger.total_row_count(), 50);
+    assert_eq!(
+        chunk_ledger.known_missing_chunk_sequence_numbers(),
+        vec![3, 6]
+    );
+}
+
+#[test]
+fn test_missing_chunk_sequence_numbers_lists_only_the_first_few() {
+    let mut chunk_ledger = new_chunk_ledger();
+    chunk_ledger.reserve(new_sequenced_ledger_entry(10000, true), None);
+    chunk_ledger.confirm(10000);
+
+    let actual = chunk_ledger.missing_chunk_sequence_numbers().unwrap();
+
+    assert_eq!(actual.len(), MAX_REPORTED_MISSING_CHUNKS);
+    assert_eq!(actual[0], 1);
+    assert_eq!(chunk_ledger.known_missing_chunk_count(), 9999);
+}
+
+fn new_chunk_ledger() -> ChunkLedger {
+    ChunkLedger::new(
+        String::from("TEST-UPLOAD-1"),
+        FileUploadChunkSource::PrimaryFileChunk,
+    )
+}
+
+fn new_ledger_entry(file_chunk_id: &str, content_hash: &str) -> ChunkLedgerEntry {
+    ChunkLedgerEntry {
+        chunk_sequence_number: 1,
+        file_chunk_id: String::from(file_chunk_id),
+        row_count: 10,
+        failed_row_count: 0,
+        content_hash: String::from(content_hash),
+        is_last_chunk: false,
+        file_hash: None,
+        is_file_hash_computed: false,
+        date_received: chrono::Utc::now().timestamp(),
+    }
+}
+
+fn new_sequenced_ledger_entry(chunk_sequence_number: i64, is_last_chunk: bool) -> ChunkLedgerEntry {
+    let mut ledger_entry = new_ledger_entry(
+        &format!("FILE-CHUNK-{}", chunk_sequence_number),
+        &format!("HASH-{}", chunk_sequence_number),
+    );
+    ledger_entry.chunk_sequence_number = chunk_sequence_number;
+    ledger_entry.is_last_chunk = is_last_chunk;
+
+    return ledger_entry;
+}
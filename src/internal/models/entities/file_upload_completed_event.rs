@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::models::entities::file_upload_verification::FileUploadVerificationOutcome;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+/**
+announces that every chunk of one file of an upload request has been accepted
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FileUploadCompletedEvent {
+    pub upload_request_id: String,
+    pub chunk_source: FileUploadChunkSource,
+    pub total_chunks: u64,
+    pub total_rows: u64,
+    pub failed_row_count: u64,
+    pub verification_outcome: FileUploadVerificationOutcome,
+    pub date_upload_started: i64,
+    pub date_upload_completed: i64,
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::models::entities::chunk_ledger::ChunkLedger;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+/**
+how far along the upload of one file (primary or comparison) of an upload request is
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FileUploadStatus {
+    pub chunk_source: FileUploadChunkSource,
+    pub chunks_received: u64,
+    pub highest_chunk_sequence_number: Option<i64>,
+
+    //only the first MAX_REPORTED_MISSING_CHUNKS of them, missing_chunk_count has them all
+    pub missing_chunk_sequence_numbers: Vec<i64>,
+    pub missing_chunk_count: u64,
+
+    pub rows_accepted: u64,
+    pub rows_failed: u64,
+    pub has_last_chunk_arrived: bool,
+    pub is_aborted: bool,
+}
+
+impl FileUploadStatus {
+    pub fn from_chunk_ledger(chunk_ledger: &ChunkLedger) -> FileUploadStatus {
+        let rows_failed = chunk_ledger.total_failed_row_count();
+
+        return FileUploadStatus {
+            chunk_source: chunk_ledger.chunk_source,
+            chunks_received: chunk_ledger.published_chunk_count,
+            highest_chunk_sequence_number: chunk_ledger.highest_chunk_sequence_number(),
+            missing_chunk_sequence_numbers: chunk_ledger.known_missing_chunk_sequence_numbers(),
+            missing_chunk_count: chunk_ledger.known_missing_chunk_count(),
+            rows_accepted: chunk_ledger.total_row_count() - rows_failed,
+            rows_failed,
+            has_last_chunk_arrived: chunk_ledger.last_chunk_sequence_number.is_some(),
+            is_aborted: chunk_ledger.is_aborted,
+        };
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+/**
+whether a fully uploaded file matches the row count and file hash it was registered with
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FileUploadVerificationOutcome {
+    pub upload_request_id: String,
+    pub chunk_source: FileUploadChunkSource,
+
+    //true if nothing failed, see file_hash_check for how far the file hash can be trusted
+    pub is_verified: bool,
+    pub expected_row_count: Option<u64>,
+    pub actual_row_count: u64,
+    pub expected_file_hash: Option<String>,
+    pub actual_file_hash: Option<String>,
+    pub file_hash_check: FileHashCheck,
+    pub failure_reasons: Vec<String>,
+    pub date_created: i64,
+}
+
+/**
+what the file hash the file was registered with was checked against
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FileHashCheck {
+    //no file hash was registered, so there was nothing to check
+    NotRegistered,
+
+    //a file hash was registered but none came with the last chunk
+    NotSent,
+
+    //the file hash that came with the last chunk is not the registered one
+    Mismatched,
+
+    //the client sent the registered file hash, the bytes it was computed from never reached us
+    ClientAsserted,
+
+    //this service hashed the file bytes it received and got the registered file hash
+    Verified,
+}
@@ -0,0 +1,15 @@
+pub mod chunk_ledger;
+pub mod file_upload_completed_event;
+pub mod file_upload_status;
+pub mod file_upload_verification;
+pub mod published_file_upload_chunk;
+pub mod service_errors;
+pub mod upload_aborted_event;
+
+#[cfg(test)]
+#[path = "./service_errors_tests.rs"]
+mod service_errors_tests;
+
+#[cfg(test)]
+#[path = "./chunk_ledger_tests.rs"]
+mod chunk_ledger_tests;
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunk;
+
+/**
+a file chunk the way it is published: the shared FileUploadChunk, with the normalised column
+values of its rows alongside since the shared row type has no field for them
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PublishedFileUploadChunk {
+    #[serde(flatten)]
+    pub file_upload_chunk: FileUploadChunk,
+
+    //empty when the recon configs ask for the raw values to be compared as they are
+    pub normalised_chunk_rows: Vec<NormalisedChunkRow>,
+}
+
+/**
+the parsed column values of one chunk row, the way the recon configs say they should be compared
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NormalisedChunkRow {
+    pub row_number: u64,
+    pub normalised_columns_from_row: Vec<String>,
+}
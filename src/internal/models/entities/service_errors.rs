@@ -0,0 +1,108 @@
+use validator::ValidationErrors;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+/**
+an error of this service, with the details a problem response reports next to the message
+kept apart from it, since the shared AppError only carries a message
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceError {
+    pub kind: ServiceErrorKind,
+    pub message: String,
+
+    //the request fields that failed validation, empty for any other error
+    pub invalid_fields: Vec<String>,
+
+    //the row of the uploaded file that caused the error, if a row did
+    pub row_number: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceErrorKind {
+    BadClientRequest,
+    Conflict,
+    ConnectionError,
+    InternalError,
+    NotFound,
+    //the service is draining before it stops, the request can be retried on another instance
+    ShuttingDown,
+    UploadAborted,
+}
+
+impl ServiceErrorKind {
+    /**
+    the stable code clients can match on, it does not change if the kind is renamed
+     */
+    pub fn code(&self) -> &'static str {
+        return match self {
+            ServiceErrorKind::BadClientRequest => "BAD_CLIENT_REQUEST",
+            ServiceErrorKind::Conflict => "CONFLICT",
+            ServiceErrorKind::ConnectionError => "CONNECTION_ERROR",
+            ServiceErrorKind::InternalError => "INTERNAL_ERROR",
+            ServiceErrorKind::NotFound => "NOT_FOUND",
+            ServiceErrorKind::ShuttingDown => "SHUTTING_DOWN",
+            ServiceErrorKind::UploadAborted => "UPLOAD_ABORTED",
+        };
+    }
+}
+
+impl ServiceError {
+    pub fn new(kind: ServiceErrorKind, message: String) -> ServiceError {
+        return ServiceError {
+            kind,
+            message,
+            invalid_fields: vec![],
+            row_number: None,
+        };
+    }
+
+    /**
+    turns failed request validation into a bad client request that still knows
+    which fields were at fault
+     */
+    pub fn validation_error(validation_errors: &ValidationErrors) -> ServiceError {
+        let mut invalid_fields: Vec<String> = validation_errors
+            .errors()
+            .keys()
+            .map(|field_name| field_name.to_string())
+            .collect();
+
+        //keep the order stable, the errors come out of a hash map
+        invalid_fields.sort();
+
+        return ServiceError {
+            kind: ServiceErrorKind::BadClientRequest,
+            message: validation_errors.to_string().replace("\n", " , "),
+            invalid_fields,
+            row_number: None,
+        };
+    }
+
+    /**
+    an error caused by one row of an uploaded file, that still knows the number of that row
+     */
+    pub fn row_error(kind: ServiceErrorKind, row_number: u64, message: String) -> ServiceError {
+        return ServiceError {
+            kind,
+            message,
+            invalid_fields: vec![],
+            row_number: Some(row_number),
+        };
+    }
+}
+
+impl From<AppError> for ServiceError {
+    fn from(app_error: AppError) -> Self {
+        let kind = match app_error.kind {
+            AppErrorKind::BadClientRequest => ServiceErrorKind::BadClientRequest,
+            AppErrorKind::ConnectionError => ServiceErrorKind::ConnectionError,
+            AppErrorKind::NotFound => ServiceErrorKind::NotFound,
+            _ => ServiceErrorKind::InternalError,
+        };
+
+        return ServiceError::new(kind, app_error.message);
+    }
+}
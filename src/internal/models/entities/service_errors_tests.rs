@@ -0,0 +1,63 @@
+use validator::Validate;
+
+use super::service_errors::{ServiceError, ServiceErrorKind};
+use crate::internal::models::view_models::requests::upload_file_chunk_request::UploadFileChunkRequest;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::{
+    app_errors::{AppError, AppErrorKind},
+    file_row::FileRow,
+    file_upload_chunk::FileUploadChunkSource,
+};
+
+#[test]
+fn test_validation_error_names_the_invalid_fields() {
+    let invalid_request = UploadFileChunkRequest {
+        upload_request_id: String::from(""),
+        chunk_sequence_number: 0,
+        chunk_source: FileUploadChunkSource::PrimaryFileChunk,
+        chunk_rows: vec![FileRow {
+            raw_data: String::from("0001,20000"),
+            row_number: 1,
+        }],
+        is_last_chunk: false,
+        file_hash: None,
+        is_file_hash_computed: false,
+    };
+
+    let validation_errors = invalid_request.validate().unwrap_err();
+
+    let actual = ServiceError::validation_error(&validation_errors);
+
+    assert_eq!(actual.kind, ServiceErrorKind::BadClientRequest);
+    assert_eq!(
+        actual.invalid_fields,
+        vec!["chunk_sequence_number", "upload_request_id"]
+    );
+    assert_eq!(actual.row_number, None);
+    assert!(!actual.message.starts_with("invalid fields"));
+}
+
+#[test]
+fn test_row_error_keeps_the_row_number_out_of_the_message() {
+    let actual = ServiceError::row_error(
+        ServiceErrorKind::BadClientRequest,
+        42,
+        String::from("not valid UTF-8"),
+    );
+
+    assert_eq!(actual.row_number, Some(42));
+    assert_eq!(actual.message, "not valid UTF-8");
+    assert!(actual.invalid_fields.is_empty());
+}
+
+#[test]
+fn test_from_app_error_keeps_kind_and_message_without_details() {
+    let actual = ServiceError::from(AppError::new(
+        AppErrorKind::ConnectionError,
+        String::from("row [1]: connection refused"),
+    ));
+
+    assert_eq!(actual.kind, ServiceErrorKind::ConnectionError);
+    assert_eq!(actual.message, "row [1]: connection refused");
+    assert_eq!(actual.row_number, None);
+    assert!(actual.invalid_fields.is_empty());
+}
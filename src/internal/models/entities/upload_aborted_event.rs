@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+/**
+tells consumers of a file's chunks queue to discard whatever they buffered for the upload
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UploadAbortedEvent {
+    pub upload_request_id: String,
+    pub chunk_source: FileUploadChunkSource,
+    pub chunks_received: u64,
+    pub date_aborted: i64,
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AbortUploadRequest {
+    //which file of the upload request to abort
+    pub source: FileUploadChunkSource,
+}
@@ -1 +1,3 @@
+pub mod abort_upload_request;
 pub mod upload_file_chunk_request;
+pub mod upload_file_request;
@@ -5,12 +5,15 @@ use crate::internal::shared_reconciler_rust_libraries::models::entities::{
     file_row::FileRow, file_upload_chunk::FileUploadChunkSource,
 };
 
+//the most chunks a file can be sent in, kept in line with the range check below
+pub const MAX_CHUNK_SEQUENCE_NUMBER: i64 = 10000;
+
 #[derive(Serialize, Deserialize, Clone, Validate, Debug)]
 pub struct UploadFileChunkRequest {
     #[validate(length(min = 1, message = "please supply an upload_request_id"))]
     pub upload_request_id: String,
 
-    #[validate(range(min = 1))]
+    #[validate(range(min = 1, max = 10000))]
     pub chunk_sequence_number: i64,
 
     pub chunk_source: FileUploadChunkSource,
@@ -19,4 +22,11 @@ pub struct UploadFileChunkRequest {
     pub chunk_rows: Vec<FileRow>,
 
     pub is_last_chunk: bool,
+
+    //hex SHA-256 of the raw bytes of the whole file, only read from the last chunk
+    pub file_hash: Option<String>,
+
+    //true only when this service hashed the file itself, a client cant set it
+    #[serde(skip)]
+    pub is_file_hash_computed: bool,
 }
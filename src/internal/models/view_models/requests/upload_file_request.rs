@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+#[derive(Serialize, Deserialize, Clone, Validate, Debug)]
+pub struct UploadFileRequest {
+    #[validate(length(min = 1, message = "please supply an upload_request_id"))]
+    pub upload_request_id: String,
+
+    pub chunk_source: FileUploadChunkSource,
+
+    #[validate(range(min = 1, max = 10000))]
+    pub chunk_size: Option<usize>,
+
+    pub has_header_row: Option<bool>,
+
+    //every char is a column delimiter of the file, as in its recon file metadata
+    #[validate(length(min = 1, message = "please supply at least one column delimiter"))]
+    pub column_delimiters: Option<String>,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AbortUploadResponse {
+    pub upload_request_id: String,
+    pub chunk_source: FileUploadChunkSource,
+    pub date_aborted: i64,
+}
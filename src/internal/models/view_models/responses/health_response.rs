@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum HealthStatus {
+    Healthy,
+    Unhealthy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DependencyHealth {
+    pub name: String,
+
+    pub status: HealthStatus,
+
+    //how long the probe took, or how long we waited before giving up on it
+    pub latency_millis: u64,
+
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HealthResponse {
+    pub status: HealthStatus,
+
+    pub dependencies: Vec<DependencyHealth>,
+}
@@ -1 +1,7 @@
+pub mod abort_upload_response;
+pub mod health_response;
+pub mod problem_details_response;
 pub mod upload_file_chunk_response;
+pub mod upload_file_chunks_response;
+pub mod upload_file_response;
+pub mod upload_status_response;
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/**
+an RFC 7807 problem details body, returned with the application/problem+json content type
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProblemDetailsResponse {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+
+    pub title: String,
+
+    pub status: u16,
+
+    pub detail: String,
+
+    //the path of the request that failed
+    pub instance: String,
+
+    //stable per kind of error, so clients can branch on it instead of on the detail
+    pub code: String,
+
+    pub correlation_id: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invalid_fields: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_number: Option<u64>,
+}
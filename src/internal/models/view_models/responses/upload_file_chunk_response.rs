@@ -3,4 +3,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UploadFileChunkResponse {
     pub file_chunk_id: String,
+
+    //chunks before the last chunk that have not arrived, known once the last chunk has arrived.
+    //only the first MAX_REPORTED_MISSING_CHUNKS of them are listed
+    pub missing_chunk_sequence_numbers: Option<Vec<i64>>,
 }
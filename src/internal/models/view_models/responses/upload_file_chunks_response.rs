@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::models::{
+    entities::service_errors::ServiceError,
+    view_models::responses::upload_file_chunk_response::UploadFileChunkResponse,
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadFileChunksResponse {
+    pub upload_request_id: String,
+
+    //one per chunk in the batch, in the order they were sent
+    pub chunk_results: Vec<UploadFileChunkResult>,
+}
+
+/**
+what happened to one chunk of a batch, either the id it was given or why it was not accepted
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadFileChunkResult {
+    pub chunk_sequence_number: i64,
+    pub chunk_source: FileUploadChunkSource,
+    pub file_chunk_id: Option<String>,
+    pub missing_chunk_sequence_numbers: Option<Vec<i64>>,
+    pub error: Option<String>,
+
+    //the same stable code and invalid fields a problem response for the chunk would carry
+    pub code: Option<String>,
+    pub invalid_fields: Vec<String>,
+}
+
+impl UploadFileChunkResult {
+    pub fn new(
+        chunk_sequence_number: i64,
+        chunk_source: FileUploadChunkSource,
+        upload_result: Result<UploadFileChunkResponse, ServiceError>,
+    ) -> UploadFileChunkResult {
+        return match upload_result {
+            Ok(upload_file_chunk_response) => UploadFileChunkResult {
+                chunk_sequence_number,
+                chunk_source,
+                file_chunk_id: Some(upload_file_chunk_response.file_chunk_id),
+                missing_chunk_sequence_numbers: upload_file_chunk_response
+                    .missing_chunk_sequence_numbers,
+                error: None,
+                code: None,
+                invalid_fields: vec![],
+            },
+            Err(e) => UploadFileChunkResult {
+                chunk_sequence_number,
+                chunk_source,
+                file_chunk_id: None,
+                missing_chunk_sequence_numbers: None,
+                error: Some(e.message),
+                code: Some(e.kind.code().to_string()),
+                invalid_fields: e.invalid_fields,
+            },
+        };
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadFileResponse {
+    pub upload_request_id: String,
+    pub chunk_source: FileUploadChunkSource,
+    pub total_rows: u64,
+    pub file_chunk_ids: Vec<String>,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::models::entities::file_upload_status::FileUploadStatus;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadStatusResponse {
+    pub upload_request_id: String,
+
+    //one per file of the upload request that has had at least one chunk accepted
+    pub file_upload_statuses: Vec<FileUploadStatus>,
+}
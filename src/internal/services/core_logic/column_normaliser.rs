@@ -0,0 +1,67 @@
+use crate::internal::{
+    models::entities::published_file_upload_chunk::{NormalisedChunkRow, PublishedFileUploadChunk},
+    shared_reconciler_rust_libraries::models::entities::{
+        file_upload_chunk::{FileUploadChunk, ReconStatus},
+        recon_tasks_models::ReconciliationConfigs,
+    },
+};
+
+/**
+pairs the chunk with the normalised view of its parsed rows, so downstream reconcilers
+dont each have to apply the recon configs themselves
+ */
+pub fn with_normalised_rows(file_upload_chunk: FileUploadChunk) -> PublishedFileUploadChunk {
+    let normalised_chunk_rows = normalise_chunk_rows(&file_upload_chunk);
+
+    return PublishedFileUploadChunk {
+        file_upload_chunk,
+        normalised_chunk_rows,
+    };
+}
+
+/**
+the normalised column values of every row that parsed, or none at all when the recon configs
+ask for the raw values to be compared as they are
+ */
+pub fn normalise_chunk_rows(file_upload_chunk: &FileUploadChunk) -> Vec<NormalisedChunkRow> {
+    let recon_config = &file_upload_chunk.recon_config;
+
+    if !recon_config.should_ignore_white_space
+        && recon_config.should_reconciliation_be_case_sensitive
+    {
+        return vec![];
+    }
+
+    return file_upload_chunk
+        .chunk_rows
+        .iter()
+        //a failed row has no column values worth comparing
+        .filter(|chunk_row| !matches!(chunk_row.recon_result, ReconStatus::Failed))
+        .map(|chunk_row| NormalisedChunkRow {
+            row_number: chunk_row.row_number,
+            normalised_columns_from_row: chunk_row
+                .parsed_columns_from_row
+                .iter()
+                .map(|column_value| normalise_column_value(column_value, recon_config))
+                .collect(),
+        })
+        .collect();
+}
+
+fn normalise_column_value(column_value: &String, recon_config: &ReconciliationConfigs) -> String {
+    let mut normalised_value = column_value.clone();
+
+    //trim the value and collapse any inner runs of white space into one space
+    if recon_config.should_ignore_white_space {
+        normalised_value = normalised_value
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+    }
+
+    if !recon_config.should_reconciliation_be_case_sensitive {
+        normalised_value = normalised_value.to_lowercase();
+    }
+
+    return normalised_value;
+}
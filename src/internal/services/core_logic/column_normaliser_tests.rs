@@ -0,0 +1,118 @@
+use crate::internal::shared_reconciler_rust_libraries::models::entities::{
+    file_chunk_queue::FileChunkQueue,
+    file_upload_chunk::{FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus},
+    recon_tasks_models::ReconciliationConfigs,
+};
+
+use super::column_normaliser::{normalise_chunk_rows, with_normalised_rows};
+
+#[test]
+fn test_normalise_chunk_rows_trims_and_lowercases_when_configured() {
+    let mut file_upload_chunk = new_file_upload_chunk(vec![new_chunk_row(
+        1,
+        vec![" 20000 ", " Kampala   UG"],
+        ReconStatus::Pending,
+    )]);
+    file_upload_chunk.recon_config.should_ignore_white_space = true;
+    file_upload_chunk
+        .recon_config
+        .should_reconciliation_be_case_sensitive = false;
+
+    let actual = normalise_chunk_rows(&file_upload_chunk);
+
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].row_number, 1);
+    assert_eq!(
+        actual[0].normalised_columns_from_row,
+        vec![String::from("20000"), String::from("kampala ug")]
+    );
+}
+
+#[test]
+fn test_normalise_chunk_rows_when_no_normalisation_configured_returns_nothing() {
+    let mut file_upload_chunk =
+        new_file_upload_chunk(vec![new_chunk_row(1, vec![" 20000"], ReconStatus::Pending)]);
+    file_upload_chunk.recon_config.should_ignore_white_space = false;
+    file_upload_chunk
+        .recon_config
+        .should_reconciliation_be_case_sensitive = true;
+
+    let actual = normalise_chunk_rows(&file_upload_chunk);
+
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn test_normalise_chunk_rows_skips_failed_rows() {
+    let file_upload_chunk = new_file_upload_chunk(vec![
+        new_chunk_row(1, vec!["0001"], ReconStatus::Pending),
+        new_chunk_row(2, vec![], ReconStatus::Failed),
+    ]);
+
+    let actual = normalise_chunk_rows(&file_upload_chunk);
+
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].row_number, 1);
+}
+
+#[test]
+fn test_with_normalised_rows_leaves_the_raw_chunk_untouched() {
+    let file_upload_chunk = new_file_upload_chunk(vec![new_chunk_row(
+        1,
+        vec![" Kampala "],
+        ReconStatus::Pending,
+    )]);
+
+    let actual = with_normalised_rows(file_upload_chunk.clone());
+
+    assert_eq!(actual.file_upload_chunk, file_upload_chunk);
+    assert_eq!(
+        actual.normalised_chunk_rows[0].normalised_columns_from_row,
+        vec![String::from("Kampala")]
+    );
+}
+
+fn new_chunk_row(
+    row_number: u64,
+    columns: Vec<&str>,
+    recon_result: ReconStatus,
+) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        raw_data: columns.join(","),
+        parsed_columns_from_row: columns.iter().map(|column| String::from(*column)).collect(),
+        recon_result,
+        recon_result_reasons: vec![],
+        row_number,
+    }
+}
+
+fn new_file_upload_chunk(chunk_rows: Vec<FileUploadChunkRow>) -> FileUploadChunk {
+    FileUploadChunk {
+        id: String::from("FILE-CHUNK-1"),
+        upload_request_id: String::from("TEST-UPLOAD-1"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::PrimaryFileChunk,
+        chunk_rows,
+        date_created: 0,
+        date_modified: 0,
+        comparison_pairs: vec![],
+        column_headers: vec![],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: true,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: true,
+        },
+        primary_file_chunks_queue: new_file_chunk_queue("src-file-chunks-queue-1"),
+        comparison_file_chunks_queue: new_file_chunk_queue("cmp-file-chunks-queue-1"),
+        result_chunks_queue: new_file_chunk_queue("recon-results-queue-1"),
+        is_last_chunk: false,
+    }
+}
+
+fn new_file_chunk_queue(topic_id: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: String::from(topic_id),
+        last_acknowledged_id: Option::None,
+    }
+}
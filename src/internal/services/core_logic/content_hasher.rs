@@ -0,0 +1,23 @@
+use sha2::{Digest, Sha256};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_row::FileRow;
+
+//keeps "a" + "bc" from hashing the same as "ab" + "c"
+const ROW_SEPARATOR: u8 = b'\n';
+
+/**
+fingerprints the rows of a chunk, so that a re-sent chunk can be told apart
+from a different chunk sent under the same sequence number
+ */
+pub fn hash_chunk_rows(chunk_rows: &[FileRow]) -> String {
+    let mut hasher = Sha256::new();
+
+    for chunk_row in chunk_rows {
+        hasher.update(chunk_row.row_number.to_string().as_bytes());
+        hasher.update([ROW_SEPARATOR]);
+        hasher.update(chunk_row.raw_data.as_bytes());
+        hasher.update([ROW_SEPARATOR]);
+    }
+
+    return format!("{:x}", hasher.finalize());
+}
@@ -0,0 +1,39 @@
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_row::FileRow;
+
+use super::content_hasher::hash_chunk_rows;
+
+#[test]
+fn test_hash_chunk_rows_is_the_same_for_the_same_rows() {
+    let chunk_rows = vec![new_file_row(1, "0001, 20000"), new_file_row(2, "0002, 30000")];
+
+    let actual = hash_chunk_rows(&chunk_rows);
+
+    assert_eq!(actual, hash_chunk_rows(&chunk_rows.clone()));
+}
+
+#[test]
+fn test_hash_chunk_rows_changes_when_a_row_changes() {
+    let chunk_rows = vec![new_file_row(1, "0001, 20000"), new_file_row(2, "0002, 30000")];
+    let tampered_chunk_rows = vec![new_file_row(1, "0001, 20000"), new_file_row(2, "0002, 30001")];
+
+    let actual = hash_chunk_rows(&tampered_chunk_rows);
+
+    assert_ne!(actual, hash_chunk_rows(&chunk_rows));
+}
+
+#[test]
+fn test_hash_chunk_rows_does_not_join_rows_together() {
+    let chunk_rows = vec![new_file_row(1, "a"), new_file_row(2, "bc")];
+    let regrouped_chunk_rows = vec![new_file_row(1, "ab"), new_file_row(2, "c")];
+
+    let actual = hash_chunk_rows(&regrouped_chunk_rows);
+
+    assert_ne!(actual, hash_chunk_rows(&chunk_rows));
+}
+
+fn new_file_row(row_number: u64, raw_data: &str) -> FileRow {
+    FileRow {
+        raw_data: String::from(raw_data),
+        row_number,
+    }
+}
@@ -0,0 +1,278 @@
+use std::mem;
+
+use sha2::{Digest, Sha256};
+
+use crate::internal::models::{
+    entities::service_errors::{ServiceError, ServiceErrorKind},
+    view_models::requests::upload_file_chunk_request::{
+        UploadFileChunkRequest, MAX_CHUNK_SEQUENCE_NUMBER,
+    },
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::{
+    file_row::FileRow, file_upload_chunk::FileUploadChunkSource,
+};
+
+const LINE_FEED: u8 = b'\n';
+const CARRIAGE_RETURN: u8 = b'\r';
+const QUOTE_CHAR: u8 = b'"';
+const DEFAULT_COLUMN_DELIMITER: char = ',';
+
+//a row longer than this is not a row the reconciler can do anything with, most likely
+//a quote that is never closed, so we stop buffering it rather than hold the whole file
+pub const MAX_ROW_BYTES: usize = 1024 * 1024;
+
+/**
+groups the lines of a streamed file into upload file chunk requests
+
+bytes can be pushed in pieces of any size; a full chunk is only handed back once
+the next row arrives, so that the final chunk can be marked as the last one.
+a line feed inside a quoted field is part of the row, not the end of it. as with the
+row tokenizer, a quote only opens a field when it starts one, so `5" pipe` stays one row.
+every byte pushed goes into the file hash sent with the last chunk
+ */
+pub struct FileChunker {
+    upload_request_id: String,
+    chunk_source: FileUploadChunkSource,
+    chunk_size: usize,
+    should_skip_header_row: bool,
+    column_delimiters: Vec<String>,
+    unfinished_line: Vec<u8>,
+    is_inside_quotes: bool,
+    is_quote_just_closed: bool,
+    rows_in_current_chunk: Vec<FileRow>,
+    held_back_chunk: Option<UploadFileChunkRequest>,
+    last_row_number: u64,
+    last_chunk_sequence_number: i64,
+    total_rows: u64,
+    file_hasher: Sha256,
+}
+
+impl FileChunker {
+    pub fn new(
+        upload_request_id: String,
+        chunk_source: FileUploadChunkSource,
+        chunk_size: usize,
+        should_skip_header_row: bool,
+        mut column_delimiters: Vec<char>,
+    ) -> FileChunker {
+        if column_delimiters.is_empty() {
+            column_delimiters.push(DEFAULT_COLUMN_DELIMITER);
+        }
+
+        return FileChunker {
+            upload_request_id,
+            chunk_source,
+            chunk_size: chunk_size.max(1),
+            should_skip_header_row,
+            column_delimiters: column_delimiters
+                .iter()
+                .map(|delimiter| delimiter.to_string())
+                .collect(),
+            unfinished_line: vec![],
+            is_inside_quotes: false,
+            is_quote_just_closed: false,
+            rows_in_current_chunk: vec![],
+            held_back_chunk: None,
+            last_row_number: 0,
+            last_chunk_sequence_number: 0,
+            total_rows: 0,
+            file_hasher: Sha256::new(),
+        };
+    }
+
+    /**
+    adds the next bytes of the file and returns the chunks that are ready to be uploaded
+
+    # Errors
+
+    This function will return an error if a row of the file is not valid UTF-8,
+    is longer than MAX_ROW_BYTES or would go in a chunk past MAX_CHUNK_SEQUENCE_NUMBER
+     */
+    pub fn push_bytes(
+        &mut self,
+        file_bytes: &[u8],
+    ) -> Result<Vec<UploadFileChunkRequest>, ServiceError> {
+        let mut ready_chunks: Vec<UploadFileChunkRequest> = vec![];
+
+        self.file_hasher.update(file_bytes);
+
+        for file_byte in file_bytes {
+            if *file_byte == QUOTE_CHAR {
+                if self.is_inside_quotes {
+                    self.is_inside_quotes = false;
+                    self.is_quote_just_closed = true;
+                } else if self.is_quote_just_closed || self.is_at_field_start() {
+                    //a doubled quote inside a quoted field closes and reopens it
+                    self.is_inside_quotes = true;
+                    self.is_quote_just_closed = false;
+                }
+            } else {
+                self.is_quote_just_closed = false;
+            }
+
+            if *file_byte != LINE_FEED || self.is_inside_quotes {
+                if self.unfinished_line.len() >= MAX_ROW_BYTES {
+                    let error_msg = format!(
+                        "this row of the uploaded file is longer than {} bytes",
+                        MAX_ROW_BYTES
+                    );
+                    return Err(ServiceError::row_error(
+                        ServiceErrorKind::BadClientRequest,
+                        self.last_row_number + 1,
+                        error_msg,
+                    ));
+                }
+
+                self.unfinished_line.push(*file_byte);
+                continue;
+            }
+
+            let line = mem::take(&mut self.unfinished_line);
+            if let Some(ready_chunk) = self.add_line(line)? {
+                ready_chunks.push(ready_chunk);
+            }
+        }
+
+        return Ok(ready_chunks);
+    }
+
+    /**
+    flushes whatever is left of the file, the last chunk returned is marked as the last chunk
+
+    # Errors
+
+    This function will return an error if the file had no rows or its last line is not valid UTF-8
+     */
+    pub fn finish(&mut self) -> Result<Vec<UploadFileChunkRequest>, ServiceError> {
+        let mut ready_chunks: Vec<UploadFileChunkRequest> = vec![];
+
+        //the file may not end with a line feed, and a quote left open runs to the end of the
+        //file, in which case the row is handed on as it is and fails when it is tokenized
+        if !self.unfinished_line.is_empty() {
+            let line = mem::take(&mut self.unfinished_line);
+            if let Some(ready_chunk) = self.add_line(line)? {
+                ready_chunks.push(ready_chunk);
+            }
+        }
+
+        if !self.rows_in_current_chunk.is_empty() {
+            if let Some(ready_chunk) = self.close_current_chunk()? {
+                ready_chunks.push(ready_chunk);
+            }
+        }
+
+        match self.held_back_chunk.take() {
+            None => {
+                let error_msg = format!(
+                    "uploaded file for upload_request_id [{}] has no rows",
+                    self.upload_request_id
+                );
+                return Err(ServiceError::new(ServiceErrorKind::BadClientRequest, error_msg));
+            }
+            Some(mut last_chunk) => {
+                let file_hasher = mem::take(&mut self.file_hasher);
+                last_chunk.is_last_chunk = true;
+                last_chunk.file_hash = Some(format!("{:x}", file_hasher.finalize()));
+                last_chunk.is_file_hash_computed = true;
+                ready_chunks.push(last_chunk);
+            }
+        }
+
+        return Ok(ready_chunks);
+    }
+
+    pub fn total_rows(&self) -> u64 {
+        return self.total_rows;
+    }
+
+    //mirrors the row tokenizer: nothing but padding has been read for the current field
+    fn is_at_field_start(&self) -> bool {
+        let end_of_padding = self
+            .unfinished_line
+            .iter()
+            .rposition(|line_byte| !line_byte.is_ascii_whitespace())
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        let line_before_padding = &self.unfinished_line[..end_of_padding];
+
+        if line_before_padding.is_empty() {
+            return true;
+        }
+
+        return self
+            .column_delimiters
+            .iter()
+            .any(|delimiter| line_before_padding.ends_with(delimiter.as_bytes()));
+    }
+
+    fn add_line(&mut self, mut line: Vec<u8>) -> Result<Option<UploadFileChunkRequest>, ServiceError> {
+        //every line counts towards the row number, even the ones we skip
+        self.last_row_number += 1;
+
+        if line.last() == Some(&CARRIAGE_RETURN) {
+            line.pop();
+        }
+
+        if self.should_skip_header_row && self.last_row_number == 1 {
+            return Ok(None);
+        }
+
+        //blank lines carry nothing to reconcile
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        let raw_data = match String::from_utf8(line) {
+            Ok(raw_data) => raw_data,
+            Err(_) => {
+                return Err(ServiceError::row_error(
+                    ServiceErrorKind::BadClientRequest,
+                    self.last_row_number,
+                    String::from("this row of the uploaded file is not valid UTF-8"),
+                ));
+            }
+        };
+
+        self.total_rows += 1;
+        self.rows_in_current_chunk.push(FileRow {
+            raw_data,
+            row_number: self.last_row_number,
+        });
+
+        if self.rows_in_current_chunk.len() < self.chunk_size {
+            return Ok(None);
+        }
+
+        return self.close_current_chunk();
+    }
+
+    fn close_current_chunk(&mut self) -> Result<Option<UploadFileChunkRequest>, ServiceError> {
+        if self.last_chunk_sequence_number >= MAX_CHUNK_SEQUENCE_NUMBER {
+            let error_msg = format!(
+                "the uploaded file takes more than {} chunks of {} rows, \
+                please send a larger chunk_size",
+                MAX_CHUNK_SEQUENCE_NUMBER, self.chunk_size
+            );
+            return Err(ServiceError::row_error(
+                ServiceErrorKind::BadClientRequest,
+                self.last_row_number,
+                error_msg,
+            ));
+        }
+
+        self.last_chunk_sequence_number += 1;
+
+        let closed_chunk = UploadFileChunkRequest {
+            upload_request_id: self.upload_request_id.clone(),
+            chunk_sequence_number: self.last_chunk_sequence_number,
+            chunk_source: self.chunk_source,
+            chunk_rows: mem::take(&mut self.rows_in_current_chunk),
+            is_last_chunk: false,
+            file_hash: None,
+            is_file_hash_computed: false,
+        };
+
+        //hand back the previous chunk now that we know it is not the last one
+        return Ok(self.held_back_chunk.replace(closed_chunk));
+    }
+}
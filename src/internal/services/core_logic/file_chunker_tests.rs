@@ -0,0 +1,183 @@
+use sha2::{Digest, Sha256};
+
+use crate::internal::models::{
+    entities::service_errors::ServiceErrorKind,
+    view_models::requests::upload_file_chunk_request::MAX_CHUNK_SEQUENCE_NUMBER,
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource;
+
+use super::file_chunker::{FileChunker, MAX_ROW_BYTES};
+
+#[test]
+fn test_file_chunker_groups_rows_into_sequenced_chunks_and_marks_last_chunk() {
+    let mut file_chunker = setup(2, false);
+
+    let mut actual = file_chunker
+        .push_bytes(b"0001,20000\n0002,30000\n0003,40000\n0004,50000\n0005,60000\n")
+        .unwrap();
+    actual.extend(file_chunker.finish().unwrap());
+
+    assert_eq!(actual.len(), 3);
+    assert_eq!(
+        actual
+            .iter()
+            .map(|chunk| chunk.chunk_sequence_number)
+            .collect::<Vec<i64>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        actual
+            .iter()
+            .map(|chunk| chunk.is_last_chunk)
+            .collect::<Vec<bool>>(),
+        vec![false, false, true]
+    );
+    assert_eq!(actual[2].chunk_rows[0].raw_data, "0005,60000");
+    assert_eq!(actual[2].chunk_rows[0].row_number, 5);
+    assert_eq!(file_chunker.total_rows(), 5);
+}
+
+#[test]
+fn test_file_chunker_holds_back_full_chunk_until_it_knows_whether_it_is_last() {
+    let mut file_chunker = setup(2, false);
+
+    let pushed = file_chunker
+        .push_bytes(b"0001,20000\n0002,30000\n")
+        .unwrap();
+    let finished = file_chunker.finish().unwrap();
+
+    assert!(pushed.is_empty());
+    assert_eq!(finished.len(), 1);
+    assert!(finished[0].is_last_chunk);
+    assert_eq!(finished[0].chunk_rows.len(), 2);
+}
+
+#[test]
+fn test_file_chunker_joins_lines_split_across_pushes() {
+    let mut file_chunker = setup(10, false);
+
+    file_chunker.push_bytes(b"0001,200").unwrap();
+    file_chunker.push_bytes(b"00\r\n0002,").unwrap();
+    file_chunker.push_bytes(b"30000").unwrap();
+    let actual = file_chunker.finish().unwrap();
+
+    assert_eq!(actual[0].chunk_rows[0].raw_data, "0001,20000");
+    assert_eq!(actual[0].chunk_rows[1].raw_data, "0002,30000");
+}
+
+#[test]
+fn test_file_chunker_sends_the_hash_of_the_raw_file_bytes_with_the_last_chunk() {
+    let mut file_chunker = setup(1, true);
+    let file_bytes = b"id,amount\r\n0001,20000\r\n\r\n0002,30000";
+
+    file_chunker.push_bytes(&file_bytes[..13]).unwrap();
+    file_chunker.push_bytes(&file_bytes[13..]).unwrap();
+    let actual = file_chunker.finish().unwrap();
+
+    let expected = format!("{:x}", Sha256::digest(file_bytes));
+    assert_eq!(actual.len(), 2);
+    assert_eq!(actual[0].file_hash, None);
+    assert_eq!(actual[1].file_hash, Some(expected));
+    assert!(actual[1].is_file_hash_computed);
+}
+
+#[test]
+fn test_file_chunker_skips_header_and_blank_lines_but_keeps_file_row_numbers() {
+    let mut file_chunker = setup(10, true);
+
+    file_chunker
+        .push_bytes(b"id,amount\n0001,20000\n\n0002,30000\n")
+        .unwrap();
+    let actual = file_chunker.finish().unwrap();
+
+    assert_eq!(actual[0].chunk_rows.len(), 2);
+    assert_eq!(actual[0].chunk_rows[0].row_number, 2);
+    assert_eq!(actual[0].chunk_rows[1].row_number, 4);
+}
+
+#[test]
+fn test_file_chunker_when_file_has_no_rows_returns_error() {
+    let mut file_chunker = setup(10, true);
+
+    file_chunker.push_bytes(b"id,amount\n").unwrap();
+    let actual = file_chunker.finish();
+
+    assert!(actual.is_err());
+}
+
+#[test]
+fn test_file_chunker_when_row_is_not_utf8_returns_error() {
+    let mut file_chunker = setup(10, false);
+
+    let actual = file_chunker.push_bytes(&[0x30, 0xff, 0xfe, b'\n']);
+
+    assert!(actual.is_err());
+}
+
+#[test]
+fn test_file_chunker_keeps_line_feeds_inside_quoted_fields_in_the_row() {
+    let mut file_chunker = setup(10, false);
+
+    file_chunker
+        .push_bytes(b"0001,\"Plot 4\nKampala\",20000\n0002,\"say \"\"hi\"\"\n\",")
+        .unwrap();
+    file_chunker.push_bytes(b"30000\n").unwrap();
+    let actual = file_chunker.finish().unwrap();
+
+    assert_eq!(actual[0].chunk_rows.len(), 2);
+    assert_eq!(actual[0].chunk_rows[0].raw_data, "0001,\"Plot 4\nKampala\",20000");
+    assert_eq!(actual[0].chunk_rows[1].raw_data, "0002,\"say \"\"hi\"\"\n\",30000");
+    assert_eq!(actual[0].chunk_rows[1].row_number, 2);
+}
+
+#[test]
+fn test_file_chunker_does_not_open_quotes_for_a_quote_inside_a_field() {
+    let mut file_chunker = setup(10, false);
+
+    file_chunker
+        .push_bytes(b"0001,5\" pipe,20000\n0002;\"Plot 4\nKampala\";30000\n")
+        .unwrap();
+    let actual = file_chunker.finish().unwrap();
+
+    assert_eq!(actual[0].chunk_rows.len(), 2);
+    assert_eq!(actual[0].chunk_rows[0].raw_data, "0001,5\" pipe,20000");
+    assert_eq!(actual[0].chunk_rows[1].raw_data, "0002;\"Plot 4\nKampala\";30000");
+    assert_eq!(actual[0].chunk_rows[1].row_number, 2);
+}
+
+#[test]
+fn test_file_chunker_when_row_is_longer_than_max_row_bytes_returns_error() {
+    let mut file_chunker = setup(10, false);
+
+    //an unclosed quote would otherwise keep swallowing the rest of the file
+    let mut file_bytes = vec![b'"'];
+    file_bytes.extend(vec![b'a'; MAX_ROW_BYTES]);
+    let actual = file_chunker.push_bytes(&file_bytes);
+
+    let error = actual.unwrap_err();
+    assert_eq!(error.kind, ServiceErrorKind::BadClientRequest);
+    assert_eq!(error.row_number, Some(1));
+    assert!(error.message.contains(&MAX_ROW_BYTES.to_string()));
+}
+
+#[test]
+fn test_file_chunker_when_file_takes_more_than_max_chunks_returns_error() {
+    let mut file_chunker = setup(1, false);
+
+    let file_bytes = b"0001,20000\n".repeat(MAX_CHUNK_SEQUENCE_NUMBER as usize + 2);
+    let actual = file_chunker.push_bytes(&file_bytes);
+
+    let error = actual.unwrap_err();
+    assert_eq!(error.kind, ServiceErrorKind::BadClientRequest);
+    assert!(error.message.contains("chunk_size"));
+}
+
+fn setup(chunk_size: usize, should_skip_header_row: bool) -> FileChunker {
+    FileChunker::new(
+        String::from("TEST-UPLOAD-1"),
+        FileUploadChunkSource::PrimaryFileChunk,
+        chunk_size,
+        should_skip_header_row,
+        vec![',', ';'],
+    )
+}
@@ -1,5 +1,30 @@
+pub mod column_normaliser;
+pub mod content_hasher;
+pub mod file_chunker;
+pub mod row_tokenizer;
 pub mod transformer;
+pub mod upload_verifier;
+
+#[cfg(test)]
+#[path = "./column_normaliser_tests.rs"]
+mod column_normaliser_tests;
+
+#[cfg(test)]
+#[path = "./content_hasher_tests.rs"]
+mod content_hasher_tests;
+
+#[cfg(test)]
+#[path = "./file_chunker_tests.rs"]
+mod file_chunker_tests;
+
+#[cfg(test)]
+#[path = "./row_tokenizer_tests.rs"]
+mod row_tokenizer_tests;
 
 #[cfg(test)]
 #[path = "./transformer_tests.rs"]
 mod transformer_tests;
+
+#[cfg(test)]
+#[path = "./upload_verifier_tests.rs"]
+mod upload_verifier_tests;
@@ -0,0 +1,122 @@
+use crate::internal::shared_reconciler_rust_libraries::common::utils::app_error_with_msg;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::{
+    app_errors::{AppError, AppErrorKind},
+    recon_tasks_models::ReconFileMetaData,
+};
+
+const DEFAULT_COLUMN_DELIMITER: char = ',';
+const DEFAULT_QUOTE_CHAR: char = '"';
+const DEFAULT_ESCAPE_CHAR: char = '"';
+
+/**
+splits a raw file row into its column values following RFC 4180
+
+any of the column delimiters separates two columns, a field wrapped in the quote char
+may contain the column delimiters, and a quote char inside such a field is written as
+escape char + quote char
+(which for the default dialect is the doubled quote `""`)
+ */
+pub struct RowTokenizer {
+    pub column_delimiters: Vec<char>,
+    pub quote_char: char,
+    pub escape_char: char,
+}
+
+impl RowTokenizer {
+    pub fn new(
+        mut column_delimiters: Vec<char>,
+        quote_char: char,
+        escape_char: char,
+    ) -> RowTokenizer {
+        if column_delimiters.is_empty() {
+            column_delimiters.push(DEFAULT_COLUMN_DELIMITER);
+        }
+
+        return RowTokenizer {
+            column_delimiters,
+            quote_char,
+            escape_char,
+        };
+    }
+
+    pub fn from_file_metadata(recon_file_meta_data: &ReconFileMetaData) -> RowTokenizer {
+        return RowTokenizer::new(
+            recon_file_meta_data.column_delimiters.clone(),
+            DEFAULT_QUOTE_CHAR,
+            DEFAULT_ESCAPE_CHAR,
+        );
+    }
+
+    /**
+    breaks up the row into its columns, reading it only once
+
+    # Errors
+
+    This function will return an error if a quoted field is never closed
+     */
+    pub fn tokenize(&self, file_row: &str) -> Result<Vec<String>, AppError> {
+        let mut columns_in_row: Vec<String> = vec![];
+        let mut current_column = String::new();
+        let mut is_inside_quotes = false;
+        let mut characters = file_row.chars().peekable();
+
+        while let Some(character) = characters.next() {
+            if is_inside_quotes {
+                //an escaped quote (or escaped escape char) is taken literally
+                if character == self.escape_char {
+                    if let Some(next_character) = characters.peek().cloned() {
+                        if next_character == self.quote_char || next_character == self.escape_char {
+                            current_column.push(next_character);
+                            characters.next();
+                            continue;
+                        }
+                    }
+                }
+
+                //otherwise the quote closes the field
+                if character == self.quote_char {
+                    is_inside_quotes = false;
+                    continue;
+                }
+
+                current_column.push(character);
+                continue;
+            }
+
+            if self.column_delimiters.contains(&character) {
+                columns_in_row.push(std::mem::take(&mut current_column));
+                continue;
+            }
+
+            //a quote only opens a field if nothing but padding has been read for it
+            if character == self.quote_char && current_column.trim().is_empty() {
+                current_column.clear();
+                is_inside_quotes = true;
+                continue;
+            }
+
+            //outside quotes a distinct escape char protects the next character
+            if character == self.escape_char && self.escape_char != self.quote_char {
+                if let Some(next_character) = characters.next() {
+                    current_column.push(next_character);
+                    continue;
+                }
+            }
+
+            current_column.push(character);
+        }
+
+        if is_inside_quotes {
+            let error_msg = format!(
+                "ParseError: quoted value in column {} is missing its closing {}",
+                columns_in_row.len(),
+                self.quote_char
+            );
+            return app_error_with_msg(AppErrorKind::BadClientRequest, &error_msg);
+        }
+
+        columns_in_row.push(current_column);
+
+        return Ok(columns_in_row);
+    }
+}
@@ -0,0 +1,88 @@
+use super::row_tokenizer::RowTokenizer;
+
+#[test]
+fn test_tokenize_splits_plain_row_on_delimiter() {
+    let tokenizer = setup_comma_tokenizer();
+
+    let actual = tokenizer.tokenize("0001, 20000, 10/02/2022").unwrap();
+
+    assert_eq!(actual, vec!["0001", " 20000", " 10/02/2022"]);
+}
+
+#[test]
+fn test_tokenize_keeps_delimiter_inside_quoted_field() {
+    let tokenizer = setup_comma_tokenizer();
+
+    let actual = tokenizer.tokenize("0001,\"Kampala, UG\",20000").unwrap();
+
+    assert_eq!(actual, vec!["0001", "Kampala, UG", "20000"]);
+}
+
+#[test]
+fn test_tokenize_opens_quoted_field_after_padding() {
+    let tokenizer = setup_comma_tokenizer();
+
+    let actual = tokenizer.tokenize("0001, \"Kampala, UG\", 20000").unwrap();
+
+    assert_eq!(actual, vec!["0001", "Kampala, UG", " 20000"]);
+}
+
+#[test]
+fn test_tokenize_unescapes_doubled_quotes() {
+    let tokenizer = setup_comma_tokenizer();
+
+    let actual = tokenizer
+        .tokenize("0001,\"the \"\"best\"\" bank, ltd\",\"\"\"\"")
+        .unwrap();
+
+    assert_eq!(actual, vec!["0001", "the \"best\" bank, ltd", "\""]);
+}
+
+#[test]
+fn test_tokenize_keeps_empty_fields() {
+    let tokenizer = setup_comma_tokenizer();
+
+    let actual = tokenizer.tokenize(",0001,,\"\",").unwrap();
+
+    assert_eq!(actual, vec!["", "0001", "", "", ""]);
+}
+
+#[test]
+fn test_tokenize_honours_custom_escape_char() {
+    let tokenizer = RowTokenizer::new(vec![';'], '\'', '\\');
+
+    let actual = tokenizer.tokenize("0001;'O\\'Brien; J';a\\;b").unwrap();
+
+    assert_eq!(actual, vec!["0001", "O'Brien; J", "a;b"]);
+}
+
+#[test]
+fn test_tokenize_when_quoted_field_is_not_closed_returns_error() {
+    let tokenizer = setup_comma_tokenizer();
+
+    let actual = tokenizer.tokenize("0001,\"Kampala, UG");
+
+    assert!(actual.is_err());
+}
+
+#[test]
+fn test_tokenize_splits_once_on_any_of_the_delimiters() {
+    let tokenizer = RowTokenizer::new(vec![',', ';'], '"', '"');
+
+    let actual = tokenizer.tokenize("0001,20000;10/02/2022").unwrap();
+
+    assert_eq!(actual, vec!["0001", "20000", "10/02/2022"]);
+}
+
+#[test]
+fn test_tokenize_when_no_delimiters_configured_defaults_to_comma() {
+    let tokenizer = RowTokenizer::new(vec![], '"', '"');
+
+    let actual = tokenizer.tokenize("0001,20000").unwrap();
+
+    assert_eq!(actual, vec!["0001", "20000"]);
+}
+
+fn setup_comma_tokenizer() -> RowTokenizer {
+    RowTokenizer::new(vec![','], '"', '"')
+}
@@ -1,7 +1,9 @@
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::internal::{
     interfaces::transformer::TransformerInterface,
+    services::core_logic::row_tokenizer::RowTokenizer,
     models::view_models::requests::upload_file_chunk_request::UploadFileChunkRequest,
     shared_reconciler_rust_libraries::models::{
         entities::{
@@ -21,6 +23,14 @@ const FILE_CHUNK_PREFIX: &'static str = "FILE-CHUNK";
 pub struct Transformer {}
 
 impl TransformerInterface for Transformer {
+    #[instrument(
+        skip_all,
+        fields(
+            upload_request_id = %upload_file_chunk_request.upload_request_id,
+            chunk_sequence_number = upload_file_chunk_request.chunk_sequence_number,
+            row_count = upload_file_chunk_request.chunk_rows.len(),
+        )
+    )]
     fn transform_into_file_upload_chunk(
         &self,
         upload_file_chunk_request: UploadFileChunkRequest,
@@ -52,9 +62,11 @@ impl TransformerInterface for Transformer {
                 &mut upload_file_chunk_request.clone(),
                 &mut file_metadata,
                 recon_task_details.task_details.comparison_pairs.clone(),
-            )?,
+            ),
             date_created: chrono::Utc::now().timestamp(),
             date_modified: chrono::Utc::now().timestamp(),
+            //comparison pairs can only name their columns by index until the shared ComparisonPair
+            //carries column header names, which could then be resolved against column_headers
             comparison_pairs: recon_task_details.task_details.comparison_pairs.clone(),
             recon_config: recon_task_details.task_details.recon_config.clone(),
             column_headers: Self::get_column_headers(upload_file_chunk_request.clone(), recon_task_details.clone()),
@@ -75,14 +87,27 @@ impl Transformer {
         upload_file_chunk_request: &mut UploadFileChunkRequest,
         recon_file_meta_data: &mut ReconFileMetaData,
         comparison_pairs: Vec<ComparisonPair>,
-    ) -> Result<Vec<FileUploadChunkRow>, AppError> {
+    ) -> Vec<FileUploadChunkRow> {
         let mut parsed_chunk_rows: Vec<FileUploadChunkRow> = vec![];
 
         for row_in_upload_file_chunk in &mut upload_file_chunk_request.chunk_rows {
-            let columns_in_row_from_upload_file_chunk = break_up_file_row_using_delimiters(
+            let columns_in_row_from_upload_file_chunk = match break_up_file_row_using_delimiters(
                 recon_file_meta_data,
                 &mut row_in_upload_file_chunk.raw_data,
-            );
+            ) {
+                Ok(columns) => columns,
+                Err(e) => {
+                    //a row we cant tokenize fails on its own without failing the chunk
+                    parsed_chunk_rows.push(FileUploadChunkRow {
+                        raw_data: row_in_upload_file_chunk.raw_data.clone(),
+                        parsed_columns_from_row: vec![],
+                        recon_result: ReconStatus::Failed,
+                        recon_result_reasons: vec![e.message],
+                        row_number: row_in_upload_file_chunk.row_number,
+                    });
+                    continue;
+                }
+            };
 
             let parsed_chunk_row = parse_colum_values_from_row(
                 upload_file_chunk_request.chunk_source,
@@ -90,12 +115,12 @@ impl Transformer {
                 row_in_upload_file_chunk.raw_data.clone(),
                 row_in_upload_file_chunk.row_number,
                 comparison_pairs.clone(),
-            )?;
+            );
 
             parsed_chunk_rows.push(parsed_chunk_row);
         }
 
-        return Ok(parsed_chunk_rows);
+        return parsed_chunk_rows;
     }
 
     fn generate_uuid(&self, prefix: &str) -> String {
@@ -135,7 +160,7 @@ fn parse_colum_values_from_row(
     upload_file_row: String,
     row_index: u64,
     comparison_pairs: Vec<ComparisonPair>,
-) -> Result<FileUploadChunkRow, AppError> {
+) -> FileUploadChunkRow {
     //set up the parsed row in a pending state
     let mut parsed_chunk_row = FileUploadChunkRow {
         raw_data: upload_file_row.to_string(),
@@ -146,84 +171,45 @@ fn parse_colum_values_from_row(
     };
 
     for comparison_pair in comparison_pairs {
-        match chunk_source {
+        let (column_index, file_description) = match chunk_source {
             FileUploadChunkSource::ComparisonFileChunk => {
-                if comparison_pair.comparison_file_column_index > upload_file_columns_in_row.len() {
-                    //skip this row because the columns we have parsed are not enough
-                    let reason = format!(
-                        "cant find a value in column {} of comparison file for this row {}",
-                        comparison_pair.comparison_file_column_index, row_index
-                    );
-                    parsed_chunk_row.recon_result = ReconStatus::Failed;
-                    parsed_chunk_row.recon_result_reasons.push(reason);
-                    continue;
-                }
-
-                //otherwise add new row to those that have been parsed
-                let row_column_value = match upload_file_columns_in_row
-                    .get(comparison_pair.comparison_file_column_index) {
-                    None => {
-                        let error_msg = format!("ParseError: unable to get row value from comparison pair comparison_file_column_index:{}", comparison_pair.primary_file_column_index.clone());
-                        return app_error_with_msg::<FileUploadChunkRow>(AppErrorKind::InternalError, &error_msg);
-                    }
-                    Some(value) => value
-                };
-
-
-                parsed_chunk_row
-                    .parsed_columns_from_row
-                    .push(row_column_value.clone());
-
-                continue;
+                (comparison_pair.comparison_file_column_index, "comparison")
             }
-
             FileUploadChunkSource::PrimaryFileChunk => {
-                if comparison_pair.primary_file_column_index > upload_file_columns_in_row.len() {
-                    //skip this row because the columns we have parsed are not enough
-                    let reason = format!(
-                        "cant find a value in column {} of source file for this row {}",
-                        comparison_pair.primary_file_column_index, row_index
-                    );
-                    parsed_chunk_row.recon_result = ReconStatus::Failed;
-                    parsed_chunk_row.recon_result_reasons.push(reason);
-                    continue;
-                }
+                (comparison_pair.primary_file_column_index, "source")
+            }
+        };
 
-                //otherwise add new row column value to those that have been parsed
-                let row_column_value = match upload_file_columns_in_row
-                    .get(comparison_pair.primary_file_column_index) {
-                    None => {
-                        let error_msg = format!("ParseError: unable to get row value from comparison pair primary_file_column_index:{}", comparison_pair.primary_file_column_index.clone());
-                        return app_error_with_msg::<FileUploadChunkRow>(AppErrorKind::InternalError, &error_msg);
-                    }
-                    Some(value) => value
-                };
+        match upload_file_columns_in_row.get(column_index) {
+            None => {
+                //fail only this row because the columns we have parsed are not enough
+                let reason = format!(
+                    "cant find a value in column {} of {} file for this row {}, it only has {} columns",
+                    column_index,
+                    file_description,
+                    row_index,
+                    upload_file_columns_in_row.len()
+                );
+                parsed_chunk_row.recon_result = ReconStatus::Failed;
+                parsed_chunk_row.recon_result_reasons.push(reason);
+            }
 
+            //otherwise add new row column value to those that have been parsed
+            Some(row_column_value) => {
                 parsed_chunk_row
                     .parsed_columns_from_row
                     .push(row_column_value.clone());
-
-                continue;
             }
         }
     }
 
-    return Ok(parsed_chunk_row);
+    return parsed_chunk_row;
 }
 
 fn break_up_file_row_using_delimiters(
     recon_file_meta_data: &mut ReconFileMetaData,
     upload_file_row: &mut String,
-) -> Vec<String> {
-    let mut upload_file_columns_in_row: Vec<String> = vec![];
-    for column_delimiter in recon_file_meta_data.column_delimiters.clone() {
-        let row_parts: Vec<String> = upload_file_row
-            .split(column_delimiter)
-            .map(str::to_owned)
-            .collect();
-
-        upload_file_columns_in_row.extend(row_parts);
-    }
-
-    upload_file_columns_in_row
+) -> Result<Vec<String>, AppError> {
+    let row_tokenizer = RowTokenizer::from_file_metadata(recon_file_meta_data);
+    row_tokenizer.tokenize(upload_file_row)
 }
@@ -4,9 +4,11 @@ use crate::internal::{
     shared_reconciler_rust_libraries::models::{
         entities::{
             file_chunk_queue::FileChunkQueue,
-            file_upload_chunk::FileUploadChunkSource,
+            file_row::FileRow,
+            file_upload_chunk::{FileUploadChunkSource, ReconStatus},
             recon_tasks_models::{
-                ReconciliationConfigs, ReconFileMetaData, ReconFileType, ReconTaskDetails,
+                ComparisonPair, ReconciliationConfigs, ReconFileMetaData, ReconFileType,
+                ReconTaskDetails,
             },
         },
         view_models::recon_task_response_details::ReconTaskResponseDetails,
@@ -33,6 +35,140 @@ async fn test_transform_into_file_upload_chunk_returns_correct_model() {
     );
 }
 
+#[actix_web::test]
+async fn test_transform_into_file_upload_chunk_keeps_quoted_delimiters_in_one_column() {
+    let to_entity_transformer = setup();
+
+    let mut upload_file_chunk_request = get_dummy_upload_file_chunk_request();
+    upload_file_chunk_request.chunk_rows = vec![FileRow {
+        raw_data: String::from("0001,\"Kampala, UG\",20000"),
+        row_number: 1,
+    }];
+
+    let mut recon_task_details = get_dummy_recon_task_details();
+    recon_task_details.task_details.comparison_pairs = vec![
+        new_same_column_index_comparison_pair(1),
+        new_same_column_index_comparison_pair(2),
+    ];
+
+    let actual = to_entity_transformer
+        .transform_into_file_upload_chunk(upload_file_chunk_request, recon_task_details)
+        .unwrap();
+
+    assert_eq!(
+        actual.chunk_rows[0].parsed_columns_from_row,
+        vec![String::from("Kampala, UG"), String::from("20000")]
+    );
+}
+
+#[actix_web::test]
+async fn test_transform_into_file_upload_chunk_when_row_has_unclosed_quote_marks_row_failed() {
+    let to_entity_transformer = setup();
+
+    let mut upload_file_chunk_request = get_dummy_upload_file_chunk_request();
+    upload_file_chunk_request.chunk_rows = vec![FileRow {
+        raw_data: String::from("0001,\"Kampala, UG,20000"),
+        row_number: 1,
+    }];
+
+    let mut recon_task_details = get_dummy_recon_task_details();
+    recon_task_details.task_details.comparison_pairs = vec![new_same_column_index_comparison_pair(0)];
+
+    let actual = to_entity_transformer
+        .transform_into_file_upload_chunk(upload_file_chunk_request, recon_task_details)
+        .unwrap();
+
+    assert_eq!(actual.chunk_rows[0].recon_result, ReconStatus::Failed);
+}
+
+#[actix_web::test]
+async fn test_transform_into_file_upload_chunk_with_many_delimiters_keeps_column_layout() {
+    let to_entity_transformer = setup();
+
+    let mut upload_file_chunk_request = get_dummy_upload_file_chunk_request();
+    upload_file_chunk_request.chunk_rows = vec![FileRow {
+        raw_data: String::from("0001,20000;10/02/2022"),
+        row_number: 1,
+    }];
+
+    let mut recon_task_details = get_dummy_recon_task_details();
+    recon_task_details.task_details.comparison_pairs = vec![new_same_column_index_comparison_pair(2)];
+    if let Some(metadata) = recon_task_details.comparison_file_metadata.as_mut() {
+        metadata.column_delimiters = vec![',', ';'];
+    }
+
+    let actual = to_entity_transformer
+        .transform_into_file_upload_chunk(upload_file_chunk_request, recon_task_details)
+        .unwrap();
+
+    assert_eq!(
+        actual.chunk_rows[0].parsed_columns_from_row,
+        vec![String::from("10/02/2022")]
+    );
+}
+
+#[actix_web::test]
+async fn test_transform_into_file_upload_chunk_when_column_index_equals_column_count_marks_row_failed() {
+    let to_entity_transformer = setup();
+
+    let mut upload_file_chunk_request = get_dummy_upload_file_chunk_request();
+    upload_file_chunk_request.chunk_rows = vec![FileRow {
+        raw_data: String::from("0001,20000"),
+        row_number: 7,
+    }];
+
+    let mut recon_task_details = get_dummy_recon_task_details();
+    recon_task_details.task_details.comparison_pairs = vec![new_same_column_index_comparison_pair(2)];
+
+    let actual = to_entity_transformer
+        .transform_into_file_upload_chunk(upload_file_chunk_request, recon_task_details)
+        .unwrap();
+
+    assert_eq!(actual.chunk_rows[0].recon_result, ReconStatus::Failed);
+    assert_eq!(actual.chunk_rows[0].recon_result_reasons.len(), 1);
+    assert!(actual.chunk_rows[0].recon_result_reasons[0].contains("column 2"));
+}
+
+#[actix_web::test]
+async fn test_transform_into_file_upload_chunk_when_one_row_is_short_keeps_other_rows() {
+    let to_entity_transformer = setup();
+
+    let mut upload_file_chunk_request = get_dummy_upload_file_chunk_request();
+    upload_file_chunk_request.chunk_rows = vec![
+        FileRow {
+            raw_data: String::from("0001,20000,10/02/2022"),
+            row_number: 1,
+        },
+        FileRow {
+            raw_data: String::from("0002"),
+            row_number: 2,
+        },
+        FileRow {
+            raw_data: String::from("0003,40000,12/02/2022"),
+            row_number: 3,
+        },
+    ];
+
+    let mut recon_task_details = get_dummy_recon_task_details();
+    recon_task_details.task_details.comparison_pairs = vec![
+        new_same_column_index_comparison_pair(0),
+        new_same_column_index_comparison_pair(1),
+    ];
+
+    let actual = to_entity_transformer
+        .transform_into_file_upload_chunk(upload_file_chunk_request, recon_task_details)
+        .unwrap();
+
+    assert_eq!(actual.chunk_rows.len(), 3);
+    assert_eq!(actual.chunk_rows[0].recon_result, ReconStatus::Pending);
+    assert_eq!(actual.chunk_rows[1].recon_result, ReconStatus::Failed);
+    assert_eq!(actual.chunk_rows[2].recon_result, ReconStatus::Pending);
+    assert_eq!(
+        actual.chunk_rows[2].parsed_columns_from_row,
+        vec![String::from("0003"), String::from("40000")]
+    );
+}
+
 fn setup() -> Transformer {
     let to_entity_transformer = Transformer {};
     return to_entity_transformer;
@@ -45,6 +181,8 @@ fn get_dummy_upload_file_chunk_request() -> UploadFileChunkRequest {
         chunk_source: FileUploadChunkSource::ComparisonFileChunk,
         chunk_rows: vec![],
         is_last_chunk: false,
+        file_hash: None,
+        is_file_hash_computed: false,
     }
 }
 
@@ -64,6 +202,14 @@ fn get_dummy_recon_task_details() -> ReconTaskResponseDetails {
                 should_ignore_white_space: true,
                 should_do_reverse_reconciliation: true,
             },
+            primary_file_chunks_queue_info: FileChunkQueue {
+                topic_id: String::from("src-file-chunks-queue-1"),
+                last_acknowledged_id: Option::None,
+            },
+            comparison_file_chunks_queue_info: FileChunkQueue {
+                topic_id: String::from("cmp-file-chunks-queue-1"),
+                last_acknowledged_id: Option::None,
+            },
             recon_results_queue_info: FileChunkQueue {
                 topic_id: String::from("recon-results-queue-1"),
                 last_acknowledged_id: Option::None,
@@ -86,7 +232,7 @@ fn get_dummy_recon_task_details() -> ReconTaskResponseDetails {
             id: String::from("cmp-file-1234"),
             file_name: String::from("cmp-file-1234"),
             row_count: 1000,
-            column_delimiters: vec![],
+            column_delimiters: vec![','],
             recon_file_type: ReconFileType::ComparisonFile,
             column_headers: vec![],
             file_hash: String::from("cmp-file-1234"),
@@ -97,3 +243,11 @@ fn get_dummy_recon_task_details() -> ReconTaskResponseDetails {
         }),
     }
 }
+
+fn new_same_column_index_comparison_pair(column_index: usize) -> ComparisonPair {
+    ComparisonPair {
+        primary_file_column_index: column_index,
+        comparison_file_column_index: column_index,
+        is_row_identifier: true,
+    }
+}
@@ -0,0 +1,76 @@
+use crate::internal::models::entities::{
+    chunk_ledger::ChunkLedger,
+    file_upload_verification::{FileHashCheck, FileUploadVerificationOutcome},
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ReconFileMetaData;
+
+/**
+checks a complete upload against the row count and file hash the file was registered with
+
+the file hash is only checked if one was registered, and is compared against the hex SHA-256
+of the raw file bytes sent with the last chunk. only a hash this service computed itself,
+as /upload-file does, counts as verified; one a client sent is reported as client asserted
+ */
+pub fn verify_upload(
+    chunk_ledger: &ChunkLedger,
+    recon_file_metadata: Option<&ReconFileMetaData>,
+) -> FileUploadVerificationOutcome {
+    let actual_row_count = chunk_ledger.total_row_count();
+    let actual_file_hash = chunk_ledger
+        .file_hash
+        .as_ref()
+        .map(|file_hash| file_hash.trim().to_lowercase());
+    let mut failure_reasons: Vec<String> = vec![];
+
+    let expected_row_count = recon_file_metadata.map(|metadata| metadata.row_count);
+    let expected_file_hash = recon_file_metadata
+        .map(|metadata| metadata.file_hash.trim().to_lowercase())
+        .filter(|file_hash| !file_hash.is_empty());
+
+    match expected_row_count {
+        None => failure_reasons.push(String::from(
+            "no file metadata was registered for this file, so it cant be verified",
+        )),
+        Some(expected_row_count) if expected_row_count != actual_row_count => {
+            failure_reasons.push(format!(
+                "expected {} rows but received {}",
+                expected_row_count, actual_row_count
+            ))
+        }
+        Some(_) => (),
+    }
+
+    let file_hash_check = match (&expected_file_hash, &actual_file_hash) {
+        (None, _) => FileHashCheck::NotRegistered,
+        (Some(_), None) => {
+            failure_reasons.push(String::from(
+                "a file hash was registered but none was sent with the last chunk",
+            ));
+            FileHashCheck::NotSent
+        }
+        (Some(expected_file_hash), Some(actual_file_hash))
+            if expected_file_hash != actual_file_hash =>
+        {
+            failure_reasons.push(format!(
+                "file hash {} does not match the hash of the uploaded file {}",
+                expected_file_hash, actual_file_hash
+            ));
+            FileHashCheck::Mismatched
+        }
+        (Some(_), Some(_)) if chunk_ledger.is_file_hash_computed => FileHashCheck::Verified,
+        (Some(_), Some(_)) => FileHashCheck::ClientAsserted,
+    };
+
+    return FileUploadVerificationOutcome {
+        upload_request_id: chunk_ledger.upload_request_id.clone(),
+        chunk_source: chunk_ledger.chunk_source,
+        is_verified: failure_reasons.is_empty(),
+        expected_row_count,
+        actual_row_count,
+        expected_file_hash,
+        actual_file_hash,
+        file_hash_check,
+        failure_reasons,
+        date_created: chrono::Utc::now().timestamp(),
+    };
+}
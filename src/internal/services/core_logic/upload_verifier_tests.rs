@@ -0,0 +1,143 @@
+use crate::internal::{
+    models::entities::{
+        chunk_ledger::{ChunkLedger, ChunkLedgerEntry},
+        file_upload_verification::FileHashCheck,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::FileUploadChunkSource,
+        recon_tasks_models::{ReconFileMetaData, ReconFileType},
+    },
+};
+
+use super::upload_verifier::verify_upload;
+
+const FILE_HASH: &'static str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+#[test]
+fn test_verify_upload_when_row_count_and_hash_match_is_verified() {
+    let chunk_ledger = dummy_complete_chunk_ledger(Some(FILE_HASH), false);
+    let file_hash = FILE_HASH.to_uppercase();
+
+    let actual = verify_upload(&chunk_ledger, Some(&dummy_file_metadata(3, &file_hash)));
+
+    assert!(actual.is_verified);
+    assert!(actual.failure_reasons.is_empty());
+    assert_eq!(actual.actual_row_count, 3);
+    assert_eq!(actual.file_hash_check, FileHashCheck::ClientAsserted);
+}
+
+#[test]
+fn test_verify_upload_when_hash_was_computed_by_the_service_reports_it_verified() {
+    let chunk_ledger = dummy_complete_chunk_ledger(Some(FILE_HASH), true);
+
+    let actual = verify_upload(&chunk_ledger, Some(&dummy_file_metadata(3, FILE_HASH)));
+
+    assert!(actual.is_verified);
+    assert_eq!(actual.file_hash_check, FileHashCheck::Verified);
+}
+
+#[test]
+fn test_verify_upload_when_row_count_differs_is_not_verified() {
+    let chunk_ledger = dummy_complete_chunk_ledger(Some(FILE_HASH), false);
+
+    let actual = verify_upload(&chunk_ledger, Some(&dummy_file_metadata(4, FILE_HASH)));
+
+    assert!(!actual.is_verified);
+    assert_eq!(actual.failure_reasons.len(), 1);
+}
+
+#[test]
+fn test_verify_upload_when_file_hash_differs_is_not_verified() {
+    let chunk_ledger = dummy_complete_chunk_ledger(Some(FILE_HASH), false);
+
+    let actual = verify_upload(&chunk_ledger, Some(&dummy_file_metadata(3, "not-the-hash")));
+
+    assert!(!actual.is_verified);
+    assert_eq!(actual.failure_reasons.len(), 1);
+}
+
+#[test]
+fn test_verify_upload_when_no_file_hash_registered_checks_only_row_count() {
+    let chunk_ledger = dummy_complete_chunk_ledger(Some(FILE_HASH), false);
+
+    let actual = verify_upload(&chunk_ledger, Some(&dummy_file_metadata(3, "")));
+
+    assert!(actual.is_verified);
+    assert_eq!(actual.expected_file_hash, None);
+}
+
+#[test]
+fn test_verify_upload_when_no_file_metadata_is_not_verified() {
+    let chunk_ledger = dummy_complete_chunk_ledger(Some(FILE_HASH), false);
+
+    let actual = verify_upload(&chunk_ledger, None);
+
+    assert!(!actual.is_verified);
+}
+
+#[test]
+fn test_verify_upload_when_file_hash_registered_but_not_sent_is_not_verified() {
+    let chunk_ledger = dummy_complete_chunk_ledger(None, false);
+
+    let actual = verify_upload(&chunk_ledger, Some(&dummy_file_metadata(3, FILE_HASH)));
+
+    assert!(!actual.is_verified);
+    assert_eq!(actual.actual_file_hash, None);
+    assert_eq!(actual.failure_reasons.len(), 1);
+}
+
+fn dummy_complete_chunk_ledger(
+    file_hash: Option<&str>,
+    is_file_hash_computed: bool,
+) -> ChunkLedger {
+    let mut chunk_ledger = ChunkLedger::new(
+        String::from("TEST-UPLOAD-1"),
+        FileUploadChunkSource::PrimaryFileChunk,
+    );
+
+    let mut last_ledger_entry = new_ledger_entry(2, 1, true);
+    last_ledger_entry.file_hash = file_hash.map(String::from);
+    last_ledger_entry.is_file_hash_computed = is_file_hash_computed;
+
+    chunk_ledger.reserve(last_ledger_entry, None);
+    chunk_ledger.reserve(new_ledger_entry(1, 2, false), None);
+    chunk_ledger.confirm(2);
+    chunk_ledger.confirm(1);
+
+    return chunk_ledger;
+}
+
+fn new_ledger_entry(
+    chunk_sequence_number: i64,
+    row_count: u64,
+    is_last_chunk: bool,
+) -> ChunkLedgerEntry {
+    ChunkLedgerEntry {
+        chunk_sequence_number,
+        file_chunk_id: format!("FILE-CHUNK-{}", chunk_sequence_number),
+        row_count,
+        failed_row_count: 0,
+        content_hash: format!("HASH-{}", chunk_sequence_number),
+        is_last_chunk,
+        file_hash: None,
+        is_file_hash_computed: false,
+        date_received: chrono::Utc::now().timestamp(),
+    }
+}
+
+fn dummy_file_metadata(row_count: u64, file_hash: &str) -> ReconFileMetaData {
+    ReconFileMetaData {
+        id: String::from("src-file-1234"),
+        file_name: String::from("src-file-1234"),
+        row_count,
+        column_delimiters: vec![','],
+        recon_file_type: ReconFileType::PrimaryFile,
+        column_headers: vec![String::from("header1"), String::from("header2")],
+        file_hash: String::from(file_hash),
+        queue_info: FileChunkQueue {
+            topic_id: String::from("src-file-chunks-queue-1"),
+            last_acknowledged_id: Option::None,
+        },
+    }
+}
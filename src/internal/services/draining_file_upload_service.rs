@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::internal::{
+    interfaces::file_chunk_upload_service::FileChunkUploadServiceInterface,
+    models::{
+        entities::service_errors::ServiceError,
+        view_models::{
+            requests::upload_file_chunk_request::UploadFileChunkRequest,
+            responses::{
+                abort_upload_response::AbortUploadResponse,
+                upload_file_chunk_response::UploadFileChunkResponse,
+                upload_file_chunks_response::UploadFileChunksResponse,
+                upload_status_response::UploadStatusResponse,
+            },
+        },
+    },
+    services::upload_drain::{InFlightUpload, UploadDrain},
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource,
+};
+
+/**
+lets shutdown wait for uploads (and the publishes they make) to finish, and turns new ones
+away once it has started, other than the chunks a file upload already under way splits off
+
+status reads are passed straight through, they have nothing to lose
+ */
+pub struct DrainingFileChunkUploadService {
+    pub inner_service: Box<dyn FileChunkUploadServiceInterface>,
+    pub upload_drain: Arc<UploadDrain>,
+}
+
+#[async_trait]
+impl FileChunkUploadServiceInterface for DrainingFileChunkUploadService {
+    async fn upload_file_chunk(
+        &self,
+        file_upload_chunk: UploadFileChunkRequest,
+    ) -> Result<UploadFileChunkResponse, ServiceError> {
+        let in_flight_upload = self.upload_drain.start_upload(format!(
+            "chunk {} of upload {} ({:?})",
+            file_upload_chunk.chunk_sequence_number,
+            file_upload_chunk.upload_request_id,
+            file_upload_chunk.chunk_source
+        ));
+
+        //the chunk never reaches the service that logs every chunk, so it is logged here
+        let _in_flight_upload = match in_flight_upload {
+            Ok(in_flight_upload) => in_flight_upload,
+            Err(e) => {
+                tracing::warn!(
+                    upload_request_id = %file_upload_chunk.upload_request_id,
+                    chunk_source = ?file_upload_chunk.chunk_source,
+                    chunk_sequence_number = file_upload_chunk.chunk_sequence_number,
+                    error_kind = ?e.kind,
+                    error = %e.message,
+                    "file chunk not uploaded"
+                );
+                return Err(e);
+            }
+        };
+
+        return self
+            .inner_service
+            .upload_file_chunk(file_upload_chunk)
+            .await;
+    }
+
+    async fn upload_file_chunk_of_file<'a>(
+        &self,
+        file_upload_chunk: UploadFileChunkRequest,
+        in_flight_file: &InFlightUpload<'a>,
+    ) -> Result<UploadFileChunkResponse, ServiceError> {
+        //the file upload is already in flight, so shutdown waits for this chunk too
+        return self
+            .inner_service
+            .upload_file_chunk_of_file(file_upload_chunk, in_flight_file)
+            .await;
+    }
+
+    async fn upload_file_chunks(
+        &self,
+        file_upload_chunks: Vec<UploadFileChunkRequest>,
+    ) -> Result<UploadFileChunksResponse, ServiceError> {
+        let upload_request_id = file_upload_chunks
+            .first()
+            .map(|file_upload_chunk| file_upload_chunk.upload_request_id.clone())
+            .unwrap_or_default();
+
+        let _in_flight_upload = self.upload_drain.start_upload(format!(
+            "batch of {} chunks of upload {}",
+            file_upload_chunks.len(),
+            upload_request_id
+        ))?;
+
+        return self
+            .inner_service
+            .upload_file_chunks(file_upload_chunks)
+            .await;
+    }
+
+    async fn get_upload_status(
+        &self,
+        upload_request_id: String,
+    ) -> Result<UploadStatusResponse, ServiceError> {
+        return self
+            .inner_service
+            .get_upload_status(upload_request_id)
+            .await;
+    }
+
+    async fn abort_upload(
+        &self,
+        upload_request_id: String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<AbortUploadResponse, ServiceError> {
+        let _in_flight_upload = self.upload_drain.start_upload(format!(
+            "abort of upload {} ({:?})",
+            upload_request_id, chunk_source
+        ))?;
+
+        return self
+            .inner_service
+            .abort_upload(upload_request_id, chunk_source)
+            .await;
+    }
+}
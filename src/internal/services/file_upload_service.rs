@@ -1,26 +1,69 @@
+use std::time::Instant;
+
 use async_trait::async_trait;
+use tracing::instrument;
 use validator::Validate;
 
 use crate::internal::{
     interfaces::{
+        chunk_ledger_repo::ChunkLedgerRepositoryInterface,
         file_chunk_upload_service::FileChunkUploadServiceInterface,
         pubsub_repo::PubSubRepositoryInterface,
         recon_tasks_repo::ReconTasksDetailsRetrieverInterface, transformer::TransformerInterface,
+        upload_status_repo::UploadStatusRepositoryInterface,
+    },
+    models::{
+        entities::{
+            chunk_ledger::{ChunkLedger, ChunkLedgerEntry, ChunkReservation},
+            file_upload_completed_event::FileUploadCompletedEvent,
+            service_errors::{ServiceError, ServiceErrorKind},
+            upload_aborted_event::UploadAbortedEvent,
+        },
+        view_models::{
+            requests::upload_file_chunk_request::UploadFileChunkRequest,
+            responses::{
+                abort_upload_response::AbortUploadResponse,
+                upload_file_chunk_response::UploadFileChunkResponse,
+                upload_file_chunks_response::{UploadFileChunkResult, UploadFileChunksResponse},
+                upload_status_response::UploadStatusResponse,
+            },
+        },
+    },
+    metrics::app_metrics::{
+        chunk_source_label, outcome_label, record_validation_rejection, FILE_CHUNKS_PUBLISHED,
+        FILE_CHUNKS_RECEIVED, ROWS_FAILED, ROWS_PARSED,
     },
-    models::view_models::{
-        requests::upload_file_chunk_request::UploadFileChunkRequest,
-        responses::upload_file_chunk_response::UploadFileChunkResponse,
+    services::{
+        core_logic::{
+            column_normaliser::with_normalised_rows, content_hasher::hash_chunk_rows,
+            upload_verifier::verify_upload,
+        },
+        upload_drain::InFlightUpload,
     },
 };
-use crate::internal::shared_reconciler_rust_libraries::models::entities::{
-    app_errors::{AppError, AppErrorKind},
-    file_upload_chunk::FileUploadChunkSource,
+use crate::internal::shared_reconciler_rust_libraries::models::{
+    entities::{
+        app_errors::AppError,
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{FileUploadChunkSource, ReconStatus},
+    },
+    view_models::recon_task_response_details::ReconTaskResponseDetails,
 };
 
+const MAX_FILE_CHUNKS_PER_BATCH: usize = 1000;
+
 pub struct FileChunkUploadService {
     pub file_upload_repo: Box<dyn PubSubRepositoryInterface>,
+    pub chunk_ledger_repo: Box<dyn ChunkLedgerRepositoryInterface>,
+    pub upload_status_repo: Box<dyn UploadStatusRepositoryInterface>,
     pub recon_tasks_retriever: Box<dyn ReconTasksDetailsRetrieverInterface>,
     pub to_entity_transformer: Box<dyn TransformerInterface>,
+
+    //where upload completed events go, the recon task's results queue if not set
+    pub file_upload_completed_topic: Option<String>,
+
+    //where the outcome of checking a complete upload against its file metadata goes
+    pub file_upload_verification_topic: String,
 }
 
 #[async_trait]
@@ -35,53 +78,685 @@ impl FileChunkUploadServiceInterface for FileChunkUploadService {
     async fn upload_file_chunk(
         &self,
         upload_file_chunk_request: UploadFileChunkRequest,
-    ) -> Result<UploadFileChunkResponse, AppError> {
-        //validate request
-        match upload_file_chunk_request.validate() {
-            Ok(_) => (),
-            Err(e) => {
-                return Err(AppError::new(
-                    AppErrorKind::BadClientRequest,
-                    e.to_string().replace("\n", " , "),
+    ) -> Result<UploadFileChunkResponse, ServiceError> {
+        FILE_CHUNKS_RECEIVED
+            .with_label_values(&[chunk_source_label(upload_file_chunk_request.chunk_source)])
+            .inc();
+
+        let started_at = Instant::now();
+        let chunk_summary = ChunkSummary::of(&upload_file_chunk_request);
+
+        let upload_result = self
+            .validate_and_upload_file_chunk(upload_file_chunk_request)
+            .await;
+
+        //this is the one log line of the chunk, failed or not, the problem response
+        //it may be turned into is not logged again
+        chunk_summary.log_outcome(&upload_result, started_at);
+
+        return upload_result;
+    }
+
+    /**
+    uploads a file chunk split off a file upload, which is no different to any other chunk here
+
+    # Errors
+
+    This function will return an error if the request fails validation or fails to be uploaded.
+     */
+    async fn upload_file_chunk_of_file<'a>(
+        &self,
+        upload_file_chunk_request: UploadFileChunkRequest,
+        _in_flight_file: &InFlightUpload<'a>,
+    ) -> Result<UploadFileChunkResponse, ServiceError> {
+        return self.upload_file_chunk(upload_file_chunk_request).await;
+    }
+
+    /**
+    uploads a batch of file chunks of the same upload request, looking up its recon task once
+
+    # Errors
+
+    This function will return an error if the batch is empty, too big, mixes upload requests or
+    the recon task cant be retrieved, otherwise each chunk gets its own result
+     */
+    async fn upload_file_chunks(
+        &self,
+        upload_file_chunk_requests: Vec<UploadFileChunkRequest>,
+    ) -> Result<UploadFileChunksResponse, ServiceError> {
+        let upload_request_id = match upload_file_chunk_requests.first() {
+            None => {
+                return Err(ServiceError::new(
+                    ServiceErrorKind::BadClientRequest,
+                    String::from("please supply at least one file chunk"),
                 ));
             }
+            Some(first_request) => first_request.upload_request_id.clone(),
+        };
+
+        if upload_file_chunk_requests.len() > MAX_FILE_CHUNKS_PER_BATCH {
+            let error_msg = format!(
+                "a batch can hold at most {} file chunks, got {}",
+                MAX_FILE_CHUNKS_PER_BATCH,
+                upload_file_chunk_requests.len()
+            );
+            return Err(ServiceError::new(ServiceErrorKind::BadClientRequest, error_msg));
+        }
+
+        if upload_file_chunk_requests
+            .iter()
+            .any(|request| request.upload_request_id != upload_request_id)
+        {
+            return Err(ServiceError::new(
+                ServiceErrorKind::BadClientRequest,
+                String::from("all file chunks in a batch must share the same upload_request_id"),
+            ));
+        }
+
+        //one lookup serves the whole batch
+        let recon_task_details = self
+            .recon_tasks_retriever
+            .get_recon_task_details(&upload_request_id)
+            .await?;
+
+        let mut chunk_results: Vec<UploadFileChunkResult> = vec![];
+
+        for upload_file_chunk_request in upload_file_chunk_requests {
+            let chunk_sequence_number = upload_file_chunk_request.chunk_sequence_number;
+            let chunk_source = upload_file_chunk_request.chunk_source;
+
+            FILE_CHUNKS_RECEIVED
+                .with_label_values(&[chunk_source_label(chunk_source)])
+                .inc();
+
+            let started_at = Instant::now();
+            let chunk_summary = ChunkSummary::of(&upload_file_chunk_request);
+
+            let upload_result = match validate_upload_file_chunk_request(&upload_file_chunk_request)
+            {
+                Ok(_) => {
+                    self.upload_chunk_for_task(upload_file_chunk_request, &recon_task_details)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+
+            //failures inside a batch never become a problem response, so they are logged here
+            chunk_summary.log_outcome(&upload_result, started_at);
+
+            chunk_results.push(UploadFileChunkResult::new(
+                chunk_sequence_number,
+                chunk_source,
+                upload_result,
+            ));
+        }
+
+        return Ok(UploadFileChunksResponse {
+            upload_request_id,
+            chunk_results,
+        });
+    }
+
+    /**
+    reports how far along the upload of each file of the upload request is
+
+    # Errors
+
+    This function will return an error if the upload_request_id is blank, no chunk was ever sent
+    for it or the status cant be read
+     */
+    async fn get_upload_status(
+        &self,
+        upload_request_id: String,
+    ) -> Result<UploadStatusResponse, ServiceError> {
+        if upload_request_id.trim().is_empty() {
+            return Err(ServiceError::new(
+                ServiceErrorKind::BadClientRequest,
+                String::from("please supply an upload_request_id"),
+            ));
+        }
+
+        let file_upload_statuses = self
+            .upload_status_repo
+            .get_file_upload_statuses(&upload_request_id)
+            .await?;
+
+        if file_upload_statuses.is_empty() {
+            let error_msg = format!("no upload found for upload_request_id [{}]", upload_request_id);
+            return Err(ServiceError::new(ServiceErrorKind::NotFound, error_msg));
+        }
+
+        return Ok(UploadStatusResponse {
+            upload_request_id,
+            file_upload_statuses,
+        });
+    }
+
+    /**
+    aborts the upload of one file of the upload request, so no more of its chunks are accepted,
+    and tells the consumers of the file's chunks queue to discard what they have
+
+    # Errors
+
+    This function will return an error if the upload is already complete or cant be aborted
+     */
+    async fn abort_upload(
+        &self,
+        upload_request_id: String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<AbortUploadResponse, ServiceError> {
+        if upload_request_id.trim().is_empty() {
+            return Err(ServiceError::new(
+                ServiceErrorKind::BadClientRequest,
+                String::from("please supply an upload_request_id"),
+            ));
+        }
+
+        let recon_task_details = self
+            .recon_tasks_retriever
+            .get_recon_task_details(&upload_request_id)
+            .await?;
+
+        let chunk_ledger = self
+            .chunk_ledger_repo
+            .abort_upload(&upload_request_id, chunk_source)
+            .await?;
+
+        //a complete upload has already been handed over to the reconciler, so it stays as it is
+        if !chunk_ledger.is_aborted {
+            let error_msg = format!(
+                "the {:?} upload [{}] is already complete and cant be aborted",
+                chunk_source, upload_request_id
+            );
+            return Err(ServiceError::new(ServiceErrorKind::Conflict, error_msg));
         }
 
+        let upload_aborted_event = UploadAbortedEvent {
+            upload_request_id: upload_request_id.clone(),
+            chunk_source,
+            chunks_received: chunk_ledger.published_chunk_count,
+            date_aborted: chunk_ledger.date_modified,
+        };
+
+        let file_chunks_queue = file_chunks_queue_of(chunk_source, &recon_task_details);
+        self.file_upload_repo
+            .publish_upload_aborted_event(&file_chunks_queue.topic_id, &upload_aborted_event)
+            .await?;
+
+        return Ok(AbortUploadResponse {
+            upload_request_id,
+            chunk_source,
+            date_aborted: upload_aborted_event.date_aborted,
+        });
+    }
+}
+
+impl FileChunkUploadService {
+    /**
+    validates the chunk and uploads it for the recon task of its upload request
+
+    # Errors
+
+    This function will return an error if the request fails validation, its recon task cant be
+    retrieved or the chunk fails to be uploaded
+     */
+    async fn validate_and_upload_file_chunk(
+        &self,
+        upload_file_chunk_request: UploadFileChunkRequest,
+    ) -> Result<UploadFileChunkResponse, ServiceError> {
+        //validate request
+        validate_upload_file_chunk_request(&upload_file_chunk_request)?;
+
         //get recon file metadata
         let recon_task_details = self
             .recon_tasks_retriever
             .get_recon_task_details(&upload_file_chunk_request.upload_request_id)
             .await?;
 
+        return self
+            .upload_chunk_for_task(upload_file_chunk_request, &recon_task_details)
+            .await;
+    }
+
+    /**
+    uploads a validated chunk once its recon task has been looked up
+
+    # Errors
+
+    This function will return an error if the task cant accept the chunk or it fails to be uploaded
+     */
+    async fn upload_chunk_for_task(
+        &self,
+        upload_file_chunk_request: UploadFileChunkRequest,
+        recon_task_details: &ReconTaskResponseDetails,
+    ) -> Result<UploadFileChunkResponse, ServiceError> {
+        //make sure the task is in a state to take this chunk
+        check_task_accepts_chunks_from(
+            recon_task_details,
+            upload_file_chunk_request.chunk_source,
+        )?;
+
+        //tells a re-sent chunk apart from a different chunk under the same sequence number
+        let content_hash = hash_chunk_rows(&upload_file_chunk_request.chunk_rows);
+        let file_hash = match upload_file_chunk_request.is_last_chunk {
+            true => upload_file_chunk_request.file_hash.clone(),
+            false => None,
+        };
+        let is_file_hash_computed =
+            file_hash.is_some() && upload_file_chunk_request.is_file_hash_computed;
+
         //transform into the repo model
-        let file_upload_chunk = self
-            .to_entity_transformer
-            .transform_into_file_upload_chunk(upload_file_chunk_request, recon_task_details)?;
+        let file_upload_chunk = self.to_entity_transformer.transform_into_file_upload_chunk(
+            upload_file_chunk_request,
+            recon_task_details.clone(),
+        )?;
+
+        let chunk_source_label = chunk_source_label(file_upload_chunk.chunk_source);
+        let failed_row_count = file_upload_chunk
+            .chunk_rows
+            .iter()
+            .filter(|chunk_row| matches!(chunk_row.recon_result, ReconStatus::Failed))
+            .count() as u64;
+
+        let mut published_file_chunk = with_normalised_rows(file_upload_chunk);
+        let file_upload_chunk = &published_file_chunk.file_upload_chunk;
+
+        //reserve the chunk in the ledger first, a chunk the ledger does not know about
+        //must never reach the queue. looking for a chunk we already accepted is part of
+        //the same update, so two requests cant both publish the same chunk
+        let chunk_reservation = self
+            .chunk_ledger_repo
+            .reserve_chunk(
+                &file_upload_chunk.upload_request_id,
+                file_upload_chunk.chunk_source,
+                ChunkLedgerEntry {
+                    chunk_sequence_number: file_upload_chunk.chunk_sequence_number,
+                    file_chunk_id: file_upload_chunk.id.clone(),
+                    row_count: file_upload_chunk.chunk_rows.len() as u64,
+                    failed_row_count,
+                    content_hash,
+                    is_last_chunk: file_upload_chunk.is_last_chunk,
+                    file_hash,
+                    is_file_hash_computed,
+                    date_received: chrono::Utc::now().timestamp(),
+                },
+            )
+            .await?;
+
+        published_file_chunk.file_upload_chunk.id = match chunk_reservation {
+            ChunkReservation::Reserved { file_chunk_id } => file_chunk_id,
 
-        let file_save_result;
+            //a chunk we have already accepted is not published a second time
+            ChunkReservation::AlreadyAccepted {
+                file_chunk_id,
+                chunk_ledger,
+            } => {
+                //the first attempt may have failed before the completion events got published
+                self.publish_completion_events_if_complete(&chunk_ledger, recon_task_details)
+                    .await?;
+
+                return Ok(UploadFileChunkResponse {
+                    file_chunk_id,
+                    missing_chunk_sequence_numbers: chunk_ledger.missing_chunk_sequence_numbers(),
+                });
+            }
+
+            ChunkReservation::InProgress => {
+                let error_msg = format!(
+                    "chunk {} of upload [{}] is still being published, please retry shortly",
+                    file_upload_chunk.chunk_sequence_number, file_upload_chunk.upload_request_id
+                );
+                return Err(ServiceError::new(ServiceErrorKind::Conflict, error_msg));
+            }
+
+            ChunkReservation::Conflict => {
+                let error_msg = format!(
+                    "chunk {} of upload [{}] was already received with different content",
+                    file_upload_chunk.chunk_sequence_number, file_upload_chunk.upload_request_id
+                );
+                return Err(ServiceError::new(ServiceErrorKind::Conflict, error_msg));
+            }
+
+            ChunkReservation::UploadAborted => {
+                let error_msg = format!(
+                    "the {:?} upload [{}] was aborted and accepts no more chunks",
+                    file_upload_chunk.chunk_source, file_upload_chunk.upload_request_id
+                );
+                return Err(ServiceError::new(ServiceErrorKind::UploadAborted, error_msg));
+            }
+        };
+        let file_upload_chunk = &published_file_chunk.file_upload_chunk;
+
+        ROWS_PARSED
+            .with_label_values(&[chunk_source_label])
+            .inc_by(file_upload_chunk.chunk_rows.len() as u64);
+        ROWS_FAILED
+            .with_label_values(&[chunk_source_label])
+            .inc_by(failed_row_count);
 
         //save it to the repository
-        match file_upload_chunk.chunk_source {
+        let file_save_result = match file_upload_chunk.chunk_source {
             FileUploadChunkSource::ComparisonFileChunk => {
-                file_save_result = self
-                    .file_upload_repo
-                    .save_file_upload_chunk_to_comparison_file_queue(&file_upload_chunk)
-                    .await;
+                self.file_upload_repo
+                    .save_file_upload_chunk_to_comparison_file_queue(&published_file_chunk)
+                    .await
             }
 
             FileUploadChunkSource::PrimaryFileChunk => {
-                file_save_result = self
-                    .file_upload_repo
-                    .save_file_upload_chunk_to_primary_file_queue(&file_upload_chunk)
-                    .await;
+                self.file_upload_repo
+                    .save_file_upload_chunk_to_primary_file_queue(&published_file_chunk)
+                    .await
+            }
+        };
+
+        if let Err(e) = file_save_result {
+            //free the sequence number so the chunk can be sent again, if this fails too
+            //the reservation stays behind and the chunk shows up as missing
+            let release_result = self
+                .chunk_ledger_repo
+                .release_chunk(
+                    &file_upload_chunk.upload_request_id,
+                    file_upload_chunk.chunk_source,
+                    file_upload_chunk.chunk_sequence_number,
+                    &file_upload_chunk.id,
+                )
+                .await;
+            if let Err(release_error) = release_result {
+                tracing::warn!(
+                    upload_request_id = %file_upload_chunk.upload_request_id,
+                    chunk_sequence_number = file_upload_chunk.chunk_sequence_number,
+                    error = %release_error.message,
+                    "reserved file chunk not released"
+                );
+            }
+
+            return Err(ServiceError::from(e));
+        }
+
+        FILE_CHUNKS_PUBLISHED
+            .with_label_values(&[chunk_source_label])
+            .inc();
+
+        //the chunk only counts towards the upload now that it is on the queue
+        let chunk_ledger = self
+            .chunk_ledger_repo
+            .confirm_chunk(
+                &file_upload_chunk.upload_request_id,
+                file_upload_chunk.chunk_source,
+                file_upload_chunk.chunk_sequence_number,
+            )
+            .await?;
+
+        //the upload may have been aborted while the chunk was being published
+        if chunk_ledger.is_aborted {
+            let error_msg = format!(
+                "the {:?} upload [{}] was aborted while chunk {} was being published",
+                file_upload_chunk.chunk_source,
+                file_upload_chunk.upload_request_id,
+                file_upload_chunk.chunk_sequence_number
+            );
+            return Err(ServiceError::new(ServiceErrorKind::UploadAborted, error_msg));
+        }
+
+        //once every chunk is in, tell the reconciler the file is complete and whether it is
+        //the file that was registered
+        self.publish_completion_events_if_complete(&chunk_ledger, recon_task_details)
+            .await?;
+
+        return Ok(UploadFileChunkResponse {
+            file_chunk_id: file_upload_chunk.id.clone(),
+            missing_chunk_sequence_numbers: chunk_ledger.missing_chunk_sequence_numbers(),
+        });
+    }
+
+    /**
+    publishes the completion events once the upload is complete, does nothing while chunks are
+    still to come or once another request has taken them on
+
+    # Errors
+
+    This function will return an error if the ledger cant be updated or either event fails to
+    be published
+     */
+    async fn publish_completion_events_if_complete(
+        &self,
+        chunk_ledger: &ChunkLedger,
+        recon_task_details: &ReconTaskResponseDetails,
+    ) -> Result<(), AppError> {
+        if !chunk_ledger.is_complete() || chunk_ledger.completion_published {
+            return Ok(());
+        }
+
+        //concurrent requests can all see the upload complete, only the one that claims it publishes
+        let is_claimed = self
+            .chunk_ledger_repo
+            .claim_completion(&chunk_ledger.upload_request_id, chunk_ledger.chunk_source)
+            .await?;
+        if !is_claimed {
+            return Ok(());
+        }
+
+        let publish_result = self
+            .publish_completion_events(chunk_ledger, recon_task_details)
+            .await;
+
+        if publish_result.is_err() {
+            //hand the claim back so that re-sending the last chunk tries again
+            let release_result = self
+                .chunk_ledger_repo
+                .release_completion(&chunk_ledger.upload_request_id, chunk_ledger.chunk_source)
+                .await;
+            if let Err(release_error) = release_result {
+                tracing::warn!(
+                    upload_request_id = %chunk_ledger.upload_request_id,
+                    chunk_source = ?chunk_ledger.chunk_source,
+                    error = %release_error.message,
+                    "completion claim not released"
+                );
             }
         }
 
-        match file_save_result {
-            Ok(_) => Ok(UploadFileChunkResponse {
-                file_chunk_id: file_upload_chunk.id,
-            }),
-            Err(e) => Err(e),
+        return publish_result;
+    }
+
+    /**
+    verifies a complete upload against its file metadata, publishes the outcome
+    on the verification topic and then announces the completed upload
+
+    # Errors
+
+    This function will return an error if either event fails to be published
+     */
+    async fn publish_completion_events(
+        &self,
+        chunk_ledger: &ChunkLedger,
+        recon_task_details: &ReconTaskResponseDetails,
+    ) -> Result<(), AppError> {
+        let recon_file_metadata = match chunk_ledger.chunk_source {
+            FileUploadChunkSource::PrimaryFileChunk => &recon_task_details.primary_file_metadata,
+            FileUploadChunkSource::ComparisonFileChunk => {
+                &recon_task_details.comparison_file_metadata
+            }
+        };
+
+        let verification_outcome = verify_upload(chunk_ledger, recon_file_metadata.as_ref());
+
+        self.file_upload_repo
+            .publish_file_upload_verification_outcome(
+                &self.file_upload_verification_topic,
+                &verification_outcome,
+            )
+            .await?;
+
+        let file_upload_completed_topic = match &self.file_upload_completed_topic {
+            Some(file_upload_completed_topic) => file_upload_completed_topic,
+            None => &recon_task_details.task_details.recon_results_queue_info.topic_id,
+        };
+
+        let file_upload_completed_event = FileUploadCompletedEvent {
+            upload_request_id: chunk_ledger.upload_request_id.clone(),
+            chunk_source: chunk_ledger.chunk_source,
+            total_chunks: chunk_ledger.published_chunk_count,
+            total_rows: chunk_ledger.total_row_count(),
+            failed_row_count: chunk_ledger.total_failed_row_count(),
+            verification_outcome,
+            date_upload_started: chunk_ledger.date_created,
+            date_upload_completed: chunk_ledger.date_modified,
+        };
+
+        self.file_upload_repo
+            .publish_file_upload_completed_event(
+                file_upload_completed_topic,
+                &file_upload_completed_event,
+            )
+            .await?;
+
+        //the task moves on once its files are in, so dont keep serving what we knew about it
+        self.recon_tasks_retriever
+            .invalidate_recon_task_details(&chunk_ledger.upload_request_id)
+            .await;
+
+        return Ok(());
+    }
+}
+
+fn file_chunks_queue_of(
+    chunk_source: FileUploadChunkSource,
+    recon_task_details: &ReconTaskResponseDetails,
+) -> &FileChunkQueue {
+    return match chunk_source {
+        FileUploadChunkSource::PrimaryFileChunk => {
+            &recon_task_details.task_details.primary_file_chunks_queue_info
+        }
+        FileUploadChunkSource::ComparisonFileChunk => {
+            &recon_task_details.task_details.comparison_file_chunks_queue_info
+        }
+    };
+}
+
+/**
+what gets logged about every chunk we are sent, taken before the request is handed over
+ */
+struct ChunkSummary {
+    upload_request_id: String,
+    chunk_source: FileUploadChunkSource,
+    chunk_sequence_number: i64,
+    row_count: usize,
+}
+
+impl ChunkSummary {
+    fn of(upload_file_chunk_request: &UploadFileChunkRequest) -> ChunkSummary {
+        return ChunkSummary {
+            upload_request_id: upload_file_chunk_request.upload_request_id.clone(),
+            chunk_source: upload_file_chunk_request.chunk_source,
+            chunk_sequence_number: upload_file_chunk_request.chunk_sequence_number,
+            row_count: upload_file_chunk_request.chunk_rows.len(),
+        };
+    }
+
+    fn log_outcome(
+        &self,
+        upload_result: &Result<UploadFileChunkResponse, ServiceError>,
+        started_at: Instant,
+    ) {
+        let duration_millis = started_at.elapsed().as_millis() as u64;
+
+        match upload_result {
+            Ok(_) => tracing::info!(
+                upload_request_id = %self.upload_request_id,
+                chunk_source = ?self.chunk_source,
+                chunk_sequence_number = self.chunk_sequence_number,
+                row_count = self.row_count,
+                outcome = outcome_label(upload_result),
+                duration_millis,
+                "file chunk uploaded"
+            ),
+            Err(e) if is_server_fault(e) => tracing::error!(
+                upload_request_id = %self.upload_request_id,
+                chunk_source = ?self.chunk_source,
+                chunk_sequence_number = self.chunk_sequence_number,
+                row_count = self.row_count,
+                outcome = outcome_label(upload_result),
+                duration_millis,
+                error_kind = ?e.kind,
+                error = %e.message,
+                "file chunk not uploaded"
+            ),
+            Err(e) => tracing::warn!(
+                upload_request_id = %self.upload_request_id,
+                chunk_source = ?self.chunk_source,
+                chunk_sequence_number = self.chunk_sequence_number,
+                row_count = self.row_count,
+                outcome = outcome_label(upload_result),
+                duration_millis,
+                error_kind = ?e.kind,
+                error = %e.message,
+                "file chunk not uploaded"
+            ),
+        }
+    }
+}
+
+//failures that are ours or a dependency's rather than the client's
+fn is_server_fault(service_error: &ServiceError) -> bool {
+    return matches!(
+        service_error.kind,
+        ServiceErrorKind::ConnectionError | ServiceErrorKind::InternalError
+    );
+}
+
+#[instrument(
+    skip_all,
+    fields(chunk_sequence_number = upload_file_chunk_request.chunk_sequence_number)
+)]
+fn validate_upload_file_chunk_request(
+    upload_file_chunk_request: &UploadFileChunkRequest,
+) -> Result<(), ServiceError> {
+    return match upload_file_chunk_request.validate() {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let service_error = ServiceError::validation_error(&e);
+            record_validation_rejection(&service_error);
+            Err(service_error)
+        }
+    };
+}
+
+/**
+admission rules for a chunk, based on the state of its recon task
+
+# Errors
+
+This function will return an error if the task is done or has no file for the chunk's source
+ */
+fn check_task_accepts_chunks_from(
+    recon_task_details: &ReconTaskResponseDetails,
+    chunk_source: FileUploadChunkSource,
+) -> Result<(), ServiceError> {
+    let task_details = &recon_task_details.task_details;
+
+    if task_details.is_done {
+        let error_msg = format!(
+            "recon task [{}] is already done and accepts no more file chunks",
+            task_details.id
+        );
+        return Err(ServiceError::new(ServiceErrorKind::BadClientRequest, error_msg));
+    }
+
+    let (file_id, file_description) = match chunk_source {
+        FileUploadChunkSource::PrimaryFileChunk => (&task_details.primary_file_id, "primary"),
+        FileUploadChunkSource::ComparisonFileChunk => {
+            (&task_details.comparison_file_id, "comparison")
         }
+    };
+
+    if file_id.is_none() {
+        let error_msg = format!(
+            "recon task [{}] has no {} file, so it cant accept {} file chunks",
+            task_details.id, file_description, file_description
+        );
+        return Err(ServiceError::new(ServiceErrorKind::BadClientRequest, error_msg));
     }
+
+    return Ok(());
 }
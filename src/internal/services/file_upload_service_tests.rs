@@ -1,3 +1,7 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::external::state_store::in_memory_chunk_ledger::InMemoryChunkLedger;
 use crate::internal::{
     interfaces::{
         file_chunk_upload_service::FileChunkUploadServiceInterface,
@@ -7,13 +11,18 @@ use crate::internal::{
         },
         transformer::{MockTransformerInterface, TransformerInterface},
     },
-    models::view_models::requests::upload_file_chunk_request::UploadFileChunkRequest,
+    models::{
+        entities::service_errors::ServiceErrorKind,
+        view_models::requests::upload_file_chunk_request::UploadFileChunkRequest,
+    },
     shared_reconciler_rust_libraries::models::{
         entities::{
             app_errors::{AppError, AppErrorKind},
             file_chunk_queue::FileChunkQueue,
             file_row::FileRow,
-            file_upload_chunk::{FileUploadChunk, FileUploadChunkSource},
+            file_upload_chunk::{
+                FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+            },
             recon_tasks_models::{
                 ComparisonPair, ReconciliationConfigs, ReconFileMetaData, ReconFileType,
                 ReconTaskDetails,
@@ -86,6 +95,736 @@ async fn given_invalid_request_returns_error() {
     assert!(actual.is_err());
 }
 
+#[actix_rt::test]
+async fn given_last_chunk_arrives_after_a_gap_returns_missing_chunk_sequence_numbers() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut first_request = dummy_valid_test_request();
+    first_request.chunk_sequence_number = 1;
+
+    let mut last_request = dummy_valid_test_request();
+    last_request.chunk_sequence_number = 3;
+    last_request.is_last_chunk = true;
+
+    let first_response = sut.upload_file_chunk(first_request).await.unwrap();
+    let last_response = sut.upload_file_chunk(last_request).await.unwrap();
+
+    assert_eq!(first_response.missing_chunk_sequence_numbers, None);
+    assert_eq!(last_response.missing_chunk_sequence_numbers, Some(vec![2]));
+}
+
+#[actix_rt::test]
+async fn given_chunk_is_resent_returns_original_file_chunk_id_without_republishing() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(1)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .times(2)
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let first_response = sut
+        .upload_file_chunk(dummy_valid_test_request())
+        .await
+        .unwrap();
+    let resent_response = sut
+        .upload_file_chunk(dummy_valid_test_request())
+        .await
+        .unwrap();
+
+    assert_eq!(resent_response.file_chunk_id, first_response.file_chunk_id);
+}
+
+#[actix_rt::test]
+async fn given_chunk_is_resent_with_different_content_returns_conflict_error() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(1)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut different_request = dummy_valid_test_request();
+    different_request.chunk_rows[0].raw_data = String::from("testing, 5678");
+
+    let _ = sut.upload_file_chunk(dummy_valid_test_request()).await;
+    let actual = sut.upload_file_chunk(different_request).await;
+
+    let error = actual.unwrap_err();
+    assert_eq!(error.kind, ServiceErrorKind::Conflict);
+}
+
+#[actix_rt::test]
+async fn given_last_chunk_completes_upload_publishes_verification_outcome() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_verification_outcome()
+        .withf(|pubsub_topic, verification_outcome| {
+            //the dummy file metadata expects 1000 rows, only 1 was sent
+            pubsub_topic == "file-upload-verifications"
+                && !verification_outcome.is_verified
+                && verification_outcome.actual_row_count == 1
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_completed_event()
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    mock_recon_tasks_repo
+        .expect_invalidate_recon_task_details()
+        .times(1)
+        .returning(|_| ());
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| {
+            let mut file_chunk = dummy_file_chunk_for_request(&request);
+            file_chunk.chunk_rows = vec![dummy_file_chunk_row()];
+            Ok(file_chunk)
+        });
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut test_request = dummy_valid_test_request();
+    test_request.chunk_sequence_number = 1;
+    test_request.is_last_chunk = true;
+
+    let actual = sut.upload_file_chunk(test_request).await;
+
+    assert!(actual.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_upload_completes_publishes_completed_event_to_configured_topic() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_verification_outcome()
+        .returning(|_, _| Ok(()));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_completed_event()
+        .withf(|pubsub_topic, file_upload_completed_event| {
+            pubsub_topic == "file-upload-completed"
+                && file_upload_completed_event.total_chunks == 2
+                && file_upload_completed_event.total_rows == 2
+                && file_upload_completed_event.failed_row_count == 1
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    mock_recon_tasks_repo
+        .expect_invalidate_recon_task_details()
+        .times(1)
+        .returning(|_| ());
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| {
+            let mut file_chunk_row = dummy_file_chunk_row();
+            if request.chunk_sequence_number == 2 {
+                file_chunk_row.recon_result = ReconStatus::Failed;
+            }
+
+            let mut file_chunk = dummy_file_chunk_for_request(&request);
+            file_chunk.chunk_rows = vec![file_chunk_row];
+            Ok(file_chunk)
+        });
+
+    let mut sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+    sut.file_upload_completed_topic = Some(String::from("file-upload-completed"));
+
+    let mut first_request = dummy_valid_test_request();
+    first_request.chunk_sequence_number = 1;
+
+    let mut last_request = dummy_valid_test_request();
+    last_request.chunk_sequence_number = 2;
+    last_request.is_last_chunk = true;
+
+    let first_response = sut.upload_file_chunk(first_request).await;
+    let last_response = sut.upload_file_chunk(last_request).await;
+
+    assert!(first_response.is_ok());
+    assert!(last_response.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_last_chunk_resent_after_completion_publishes_completion_events_once() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(1)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_verification_outcome()
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_completed_event()
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    mock_recon_tasks_repo
+        .expect_invalidate_recon_task_details()
+        .times(1)
+        .returning(|_| ());
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut last_request = dummy_valid_test_request();
+    last_request.chunk_sequence_number = 1;
+    last_request.is_last_chunk = true;
+
+    let first_response = sut.upload_file_chunk(last_request.clone()).await;
+    let resent_response = sut.upload_file_chunk(last_request).await;
+
+    assert!(first_response.is_ok());
+    assert!(resent_response.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_completed_event_fails_to_publish_resent_last_chunk_publishes_it_again() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(1)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_verification_outcome()
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let publish_attempts = Arc::new(AtomicUsize::new(0));
+    mock_file_upload_repo
+        .expect_publish_file_upload_completed_event()
+        .times(2)
+        .returning(move |_, _| match publish_attempts.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                "unable to connect".to_string(),
+            )),
+            _ => Ok(()),
+        });
+
+    mock_recon_tasks_repo
+        .expect_invalidate_recon_task_details()
+        .times(1)
+        .returning(|_| ());
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut last_request = dummy_valid_test_request();
+    last_request.chunk_sequence_number = 1;
+    last_request.is_last_chunk = true;
+
+    let first_response = sut.upload_file_chunk(last_request.clone()).await;
+    let resent_response = sut.upload_file_chunk(last_request).await;
+
+    assert!(first_response.is_err());
+    assert!(resent_response.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_chunks_uploaded_get_upload_status_reports_progress_per_file() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| {
+            let mut file_chunk = dummy_file_chunk_for_request(&request);
+            file_chunk.chunk_rows = vec![dummy_file_chunk_row()];
+            Ok(file_chunk)
+        });
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    for chunk_sequence_number in [1, 3] {
+        let mut test_request = dummy_valid_test_request();
+        test_request.chunk_sequence_number = chunk_sequence_number;
+        sut.upload_file_chunk(test_request).await.unwrap();
+    }
+
+    let actual = sut
+        .get_upload_status(String::from("1234"))
+        .await
+        .unwrap();
+
+    assert_eq!(actual.file_upload_statuses.len(), 1);
+    let file_upload_status = &actual.file_upload_statuses[0];
+    assert_eq!(file_upload_status.chunks_received, 2);
+    assert_eq!(file_upload_status.highest_chunk_sequence_number, Some(3));
+    assert_eq!(file_upload_status.missing_chunk_sequence_numbers, vec![2]);
+    assert_eq!(file_upload_status.rows_accepted, 2);
+    assert!(!file_upload_status.has_last_chunk_arrived);
+}
+
+#[actix_rt::test]
+async fn given_unknown_upload_request_id_get_upload_status_returns_not_found() {
+    let (mock_file_upload_repo, mock_recon_tasks_repo, mock_to_entity_transformer) =
+        setup_dependencies();
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let actual = sut.get_upload_status(String::from("UNKNOWN-UPLOAD")).await;
+
+    assert_eq!(actual.unwrap_err().kind, ServiceErrorKind::NotFound);
+}
+
+#[actix_rt::test]
+async fn given_blank_upload_request_id_get_upload_status_returns_error() {
+    let (mock_file_upload_repo, mock_recon_tasks_repo, mock_to_entity_transformer) =
+        setup_dependencies();
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let actual = sut.get_upload_status(String::from(" ")).await;
+
+    assert!(actual.is_err());
+}
+
+#[actix_rt::test]
+async fn given_upload_aborted_publishes_aborted_event_and_rejects_further_chunks() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(1)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_file_upload_repo
+        .expect_publish_upload_aborted_event()
+        .withf(|pubsub_topic, upload_aborted_event| {
+            pubsub_topic == "cmp-file-chunks-queue-1" && upload_aborted_event.chunks_received == 1
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut first_request = dummy_valid_test_request();
+    first_request.chunk_sequence_number = 1;
+    sut.upload_file_chunk(first_request).await.unwrap();
+
+    let abort_response = sut
+        .abort_upload(
+            String::from("1234"),
+            FileUploadChunkSource::ComparisonFileChunk,
+        )
+        .await;
+
+    let actual = sut.upload_file_chunk(dummy_valid_test_request()).await;
+
+    assert!(abort_response.is_ok());
+    let error = actual.unwrap_err();
+    assert_eq!(error.kind, ServiceErrorKind::UploadAborted);
+}
+
+#[actix_rt::test]
+async fn given_upload_already_complete_abort_upload_returns_error() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_verification_outcome()
+        .returning(|_, _| Ok(()));
+
+    mock_file_upload_repo
+        .expect_publish_file_upload_completed_event()
+        .returning(|_, _| Ok(()));
+
+    mock_file_upload_repo.expect_publish_upload_aborted_event().never();
+
+    mock_recon_tasks_repo
+        .expect_invalidate_recon_task_details()
+        .times(1)
+        .returning(|_| ());
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut last_request = dummy_valid_test_request();
+    last_request.chunk_sequence_number = 1;
+    last_request.is_last_chunk = true;
+    sut.upload_file_chunk(last_request).await.unwrap();
+
+    let actual = sut
+        .abort_upload(
+            String::from("1234"),
+            FileUploadChunkSource::ComparisonFileChunk,
+        )
+        .await;
+
+    assert_eq!(actual.unwrap_err().kind, ServiceErrorKind::Conflict);
+}
+
+#[actix_rt::test]
+async fn given_recon_task_is_done_rejects_chunk_without_publishing() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| {
+            let mut recon_task_details = dummy_success_recon_task_details();
+            recon_task_details.task_details.is_done = true;
+            Ok(recon_task_details)
+        });
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .never();
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .never();
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let actual = sut.upload_file_chunk(dummy_valid_test_request()).await;
+
+    let error = actual.unwrap_err();
+    assert_eq!(error.kind, ServiceErrorKind::BadClientRequest);
+    assert_eq!(
+        error.message,
+        "recon task [task-1234] is already done and accepts no more file chunks"
+    );
+}
+
+#[actix_rt::test]
+async fn given_recon_task_has_no_comparison_file_rejects_comparison_chunk() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| {
+            let mut recon_task_details = dummy_success_recon_task_details();
+            recon_task_details.task_details.comparison_file_id = None;
+            Ok(recon_task_details)
+        });
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .never();
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .never();
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let actual = sut.upload_file_chunk(dummy_valid_test_request()).await;
+
+    let error = actual.unwrap_err();
+    assert_eq!(error.kind, ServiceErrorKind::BadClientRequest);
+    assert_eq!(
+        error.message,
+        "recon task [task-1234] has no comparison file, so it cant accept comparison file chunks"
+    );
+}
+
+#[actix_rt::test]
+async fn given_recon_task_has_no_comparison_file_still_accepts_primary_chunk() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| {
+            let mut recon_task_details = dummy_success_recon_task_details();
+            recon_task_details.task_details.comparison_file_id = None;
+            Ok(recon_task_details)
+        });
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_primary_file_queue()
+        .times(1)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| {
+            let mut file_chunk = dummy_file_chunk_for_request(&request);
+            file_chunk.chunk_source = FileUploadChunkSource::PrimaryFileChunk;
+            Ok(file_chunk)
+        });
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut test_request = dummy_valid_test_request();
+    test_request.chunk_source = FileUploadChunkSource::PrimaryFileChunk;
+
+    let actual = sut.upload_file_chunk(test_request).await;
+
+    assert!(actual.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_batch_of_chunks_looks_up_task_once_and_returns_result_per_chunk() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .times(1)
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(2)
+        .returning(|_y| Ok(String::from("FILE_CHUNK_1234")));
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| {
+            let mut file_chunk = dummy_file_chunk_for_request(&request);
+            file_chunk.id = format!("FILE-CHUNK-{}", request.chunk_sequence_number);
+            Ok(file_chunk)
+        });
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    //the middle chunk fails validation, the others still go through
+    let test_requests: Vec<UploadFileChunkRequest> = [1, 0, 3]
+        .iter()
+        .map(|chunk_sequence_number| {
+            let mut test_request = dummy_valid_test_request();
+            test_request.chunk_sequence_number = *chunk_sequence_number;
+            test_request
+        })
+        .collect();
+
+    let actual = sut.upload_file_chunks(test_requests).await.unwrap();
+
+    assert_eq!(actual.chunk_results.len(), 3);
+    assert_eq!(actual.chunk_results[0].file_chunk_id, Some(String::from("FILE-CHUNK-1")));
+    assert!(actual.chunk_results[1].file_chunk_id.is_none());
+    assert!(actual.chunk_results[1].error.is_some());
+    assert_eq!(
+        actual.chunk_results[1].code,
+        Some(String::from("BAD_CLIENT_REQUEST"))
+    );
+    assert_eq!(
+        actual.chunk_results[1].invalid_fields,
+        vec!["chunk_sequence_number"]
+    );
+    assert_eq!(actual.chunk_results[2].file_chunk_id, Some(String::from("FILE-CHUNK-3")));
+}
+
+#[actix_rt::test]
+async fn given_batch_mixes_upload_requests_returns_error() {
+    let (mock_file_upload_repo, mut mock_recon_tasks_repo, mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo.expect_get_recon_task_details().never();
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let mut other_upload_request = dummy_valid_test_request();
+    other_upload_request.upload_request_id = String::from("5678");
+
+    let actual = sut
+        .upload_file_chunks(vec![dummy_valid_test_request(), other_upload_request])
+        .await;
+
+    assert!(actual.is_err());
+}
+
+#[actix_rt::test]
+async fn given_empty_batch_returns_error() {
+    let (mock_file_upload_repo, mock_recon_tasks_repo, mock_to_entity_transformer) =
+        setup_dependencies();
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let actual = sut.upload_file_chunks(vec![]).await;
+
+    assert!(actual.is_err());
+}
+
 #[actix_rt::test]
 async fn given_valid_request_but_repo_returns_error_returns_error() {
     let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
@@ -121,6 +860,51 @@ async fn given_valid_request_but_repo_returns_error_returns_error() {
     assert!(actual.is_err());
 }
 
+#[actix_rt::test]
+async fn given_publish_fails_resent_chunk_is_published_again() {
+    let (mut mock_file_upload_repo, mut mock_recon_tasks_repo, mut mock_to_entity_transformer) =
+        setup_dependencies();
+
+    mock_recon_tasks_repo
+        .expect_get_recon_task_details()
+        .returning(|_y| Ok(dummy_success_recon_task_details()));
+
+    let publish_attempts = Arc::new(AtomicUsize::new(0));
+    let publish_attempts_seen = publish_attempts.clone();
+    mock_file_upload_repo
+        .expect_save_file_upload_chunk_to_comparison_file_queue()
+        .times(2)
+        .returning(move |_y| match publish_attempts.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                "unable to connect".to_string(),
+            )),
+            _ => Ok(String::from("FILE_CHUNK_1234")),
+        });
+
+    mock_to_entity_transformer
+        .expect_transform_into_file_upload_chunk()
+        .returning(|request, _| Ok(dummy_file_chunk_for_request(&request)));
+
+    let sut = setup_service_under_test(
+        mock_file_upload_repo,
+        mock_recon_tasks_repo,
+        mock_to_entity_transformer,
+    );
+
+    let first_attempt = sut.upload_file_chunk(dummy_valid_test_request()).await;
+    let status_after_failure = sut
+        .get_upload_status(dummy_valid_test_request().upload_request_id)
+        .await
+        .unwrap();
+    let second_attempt = sut.upload_file_chunk(dummy_valid_test_request()).await;
+
+    assert!(first_attempt.is_err());
+    assert_eq!(status_after_failure.file_upload_statuses[0].chunks_received, 0);
+    assert!(second_attempt.is_ok());
+    assert_eq!(publish_attempts_seen.load(Ordering::SeqCst), 2);
+}
+
 fn setup_dependencies() -> (
     Box<MockPubSubRepositoryInterface>,
     Box<MockReconTasksDetailsRetrieverInterface>,
@@ -147,6 +931,14 @@ fn dummy_success_recon_task_details() -> ReconTaskResponseDetails {
             has_begun: true,
             comparison_pairs: vec![new_same_column_index_comparison_pair(0)],
             recon_config: default_recon_configs(),
+            primary_file_chunks_queue_info: FileChunkQueue {
+                topic_id: String::from("src-file-chunks-queue-1"),
+                last_acknowledged_id: Option::None,
+            },
+            comparison_file_chunks_queue_info: FileChunkQueue {
+                topic_id: String::from("cmp-file-chunks-queue-1"),
+                last_acknowledged_id: Option::None,
+            },
             recon_results_queue_info: FileChunkQueue {
                 topic_id: String::from("recon-results-queue-1"),
                 last_acknowledged_id: Option::None,
@@ -191,6 +983,8 @@ fn dummy_valid_test_request() -> UploadFileChunkRequest {
             row_number: 1,
         }],
         is_last_chunk: false,
+        file_hash: None,
+        is_file_hash_computed: false,
     }
 }
 
@@ -222,15 +1016,39 @@ fn dummy_valid_file_chunk() -> FileUploadChunk {
     }
 }
 
+fn dummy_file_chunk_for_request(request: &UploadFileChunkRequest) -> FileUploadChunk {
+    let mut file_chunk = dummy_valid_file_chunk();
+    file_chunk.upload_request_id = request.upload_request_id.clone();
+    file_chunk.chunk_sequence_number = request.chunk_sequence_number;
+    file_chunk.is_last_chunk = request.is_last_chunk;
+    return file_chunk;
+}
+
+fn dummy_file_chunk_row() -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        raw_data: String::from("testing, 1234"),
+        parsed_columns_from_row: vec![String::from("testing"), String::from(" 1234")],
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+        row_number: 1,
+    }
+}
+
 fn setup_service_under_test(
     pubsub: Box<dyn PubSubRepositoryInterface>,
     recon_tasks_repo: Box<dyn ReconTasksDetailsRetrieverInterface>,
     to_entity_transformer: Box<dyn TransformerInterface>,
 ) -> FileChunkUploadService {
+    let chunk_ledger = InMemoryChunkLedger::new();
+
     FileChunkUploadService {
         file_upload_repo: pubsub,
+        chunk_ledger_repo: Box::new(chunk_ledger.clone()),
+        upload_status_repo: Box::new(chunk_ledger),
         recon_tasks_retriever: recon_tasks_repo,
         to_entity_transformer: to_entity_transformer,
+        file_upload_completed_topic: None,
+        file_upload_verification_topic: String::from("file-upload-verifications"),
     }
 }
 
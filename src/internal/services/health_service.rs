@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+
+use crate::internal::{
+    interfaces::health_check::{DependencyHealthCheckInterface, HealthServiceInterface},
+    models::view_models::responses::health_response::{
+        DependencyHealth, HealthResponse, HealthStatus,
+    },
+};
+
+pub struct HealthService {
+    pub dependency_health_checks: Vec<Box<dyn DependencyHealthCheckInterface>>,
+
+    //a probe that takes longer than this counts as a failed one
+    pub health_check_timeout: Duration,
+}
+
+#[async_trait]
+impl HealthServiceInterface for HealthService {
+    /**
+    probes every dependency at the same time, the service is only ready if all of them are
+     */
+    async fn check_readiness(&self) -> HealthResponse {
+        let dependencies = join_all(self.dependency_health_checks.iter().map(
+            |dependency_health_check| self.check_dependency(dependency_health_check.as_ref()),
+        ))
+        .await;
+
+        let status = if dependencies
+            .iter()
+            .all(|dependency| dependency.status == HealthStatus::Healthy)
+        {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy
+        };
+
+        return HealthResponse {
+            status,
+            dependencies,
+        };
+    }
+}
+
+impl HealthService {
+    async fn check_dependency(
+        &self,
+        dependency_health_check: &dyn DependencyHealthCheckInterface,
+    ) -> DependencyHealth {
+        let started_at = Instant::now();
+
+        let check_result = tokio::time::timeout(
+            self.health_check_timeout,
+            dependency_health_check.check_health(),
+        )
+        .await;
+
+        let latency_millis = started_at.elapsed().as_millis() as u64;
+
+        let (status, error) = match check_result {
+            Ok(Ok(_)) => (HealthStatus::Healthy, None),
+            Ok(Err(e)) => (HealthStatus::Unhealthy, Some(e.message)),
+            Err(_) => (
+                HealthStatus::Unhealthy,
+                Some(format!(
+                    "no answer within {} ms",
+                    self.health_check_timeout.as_millis()
+                )),
+            ),
+        };
+
+        return DependencyHealth {
+            name: dependency_health_check.dependency_name(),
+            status,
+            latency_millis,
+            error,
+        };
+    }
+}
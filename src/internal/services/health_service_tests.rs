@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::internal::{
+    interfaces::health_check::{
+        DependencyHealthCheckInterface, HealthServiceInterface, MockDependencyHealthCheckInterface,
+    },
+    models::view_models::responses::health_response::HealthStatus,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+use super::health_service::HealthService;
+
+#[actix_rt::test]
+async fn given_all_dependencies_are_healthy_reports_ready() {
+    let sut = setup_service_under_test(vec![
+        Box::new(healthy_dependency("dapr-sidecar")),
+        Box::new(healthy_dependency("recon-tasks-service")),
+    ]);
+
+    let actual = sut.check_readiness().await;
+
+    assert_eq!(actual.status, HealthStatus::Healthy);
+    assert_eq!(actual.dependencies.len(), 2);
+    assert_eq!(actual.dependencies[0].name, "dapr-sidecar");
+    assert!(actual.dependencies[0].error.is_none());
+}
+
+#[actix_rt::test]
+async fn given_one_dependency_is_unhealthy_reports_not_ready() {
+    let mut unhealthy_dependency = MockDependencyHealthCheckInterface::new();
+    unhealthy_dependency
+        .expect_dependency_name()
+        .returning(|| String::from("recon-tasks-service"));
+    unhealthy_dependency.expect_check_health().returning(|| {
+        Err(AppError::new(
+            AppErrorKind::ConnectionError,
+            String::from("connection refused"),
+        ))
+    });
+
+    let sut = setup_service_under_test(vec![
+        Box::new(healthy_dependency("dapr-sidecar")),
+        Box::new(unhealthy_dependency),
+    ]);
+
+    let actual = sut.check_readiness().await;
+
+    assert_eq!(actual.status, HealthStatus::Unhealthy);
+    assert_eq!(actual.dependencies[0].status, HealthStatus::Healthy);
+    assert_eq!(actual.dependencies[1].status, HealthStatus::Unhealthy);
+    assert_eq!(
+        actual.dependencies[1].error,
+        Some(String::from("connection refused"))
+    );
+}
+
+#[actix_rt::test]
+async fn given_dependency_does_not_answer_in_time_reports_not_ready() {
+    let sut = setup_service_under_test(vec![Box::new(HangingDependency {})]);
+
+    let actual = sut.check_readiness().await;
+
+    assert_eq!(actual.status, HealthStatus::Unhealthy);
+    assert!(actual.dependencies[0].error.is_some());
+}
+
+struct HangingDependency {}
+
+#[async_trait]
+impl DependencyHealthCheckInterface for HangingDependency {
+    fn dependency_name(&self) -> String {
+        return String::from("hanging-dependency");
+    }
+
+    async fn check_health(&self) -> Result<(), AppError> {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        return Ok(());
+    }
+}
+
+fn healthy_dependency(dependency_name: &'static str) -> MockDependencyHealthCheckInterface {
+    let mut dependency = MockDependencyHealthCheckInterface::new();
+    dependency
+        .expect_dependency_name()
+        .returning(move || String::from(dependency_name));
+    dependency.expect_check_health().returning(|| Ok(()));
+    return dependency;
+}
+
+fn setup_service_under_test(
+    dependency_health_checks: Vec<Box<dyn DependencyHealthCheckInterface>>,
+) -> HealthService {
+    return HealthService {
+        dependency_health_checks,
+        health_check_timeout: Duration::from_millis(50),
+    };
+}
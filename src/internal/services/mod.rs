@@ -1,6 +1,17 @@
 pub mod core_logic;
+pub mod draining_file_upload_service;
 pub mod file_upload_service;
+pub mod health_service;
+pub mod upload_drain;
 
 #[cfg(test)]
 #[path = "./file_upload_service_tests.rs"]
 mod file_upload_service_tests;
+
+#[cfg(test)]
+#[path = "./health_service_tests.rs"]
+mod health_service_tests;
+
+#[cfg(test)]
+#[path = "./upload_drain_tests.rs"]
+mod upload_drain_tests;
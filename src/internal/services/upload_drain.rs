@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use crate::internal::{
+    interfaces::health_check::DependencyHealthCheckInterface,
+    models::entities::service_errors::{ServiceError, ServiceErrorKind},
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_upload_chunk::FileUploadChunkSource,
+    },
+};
+
+const SHUTDOWN_DEPENDENCY_NAME: &'static str = "shutdown";
+
+/**
+keeps track of the uploads in flight, so that shutdown can wait for them to finish
+
+once draining has started no new uploads are let in
+ */
+pub struct UploadDrain {
+    drain_state: Mutex<DrainState>,
+
+    //woken every time an upload finishes
+    upload_finished: Notify,
+}
+
+struct DrainState {
+    is_draining: bool,
+
+    next_upload_id: u64,
+
+    //what each in-flight upload is, so anything abandoned can be logged
+    in_flight_uploads: HashMap<u64, String>,
+}
+
+/**
+an upload that counts as in flight until this is dropped
+ */
+pub struct InFlightUpload<'a> {
+    upload_drain: &'a UploadDrain,
+    upload_id: u64,
+}
+
+impl UploadDrain {
+    pub fn new() -> UploadDrain {
+        return UploadDrain {
+            drain_state: Mutex::new(DrainState {
+                is_draining: false,
+                next_upload_id: 0,
+                in_flight_uploads: HashMap::new(),
+            }),
+            upload_finished: Notify::new(),
+        };
+    }
+
+    /**
+    registers an upload as in flight
+
+    # Errors
+
+    This function will return an error if draining has already started
+     */
+    pub fn start_upload(&self, upload_description: String) -> Result<InFlightUpload<'_>, ServiceError> {
+        let mut drain_state = self.lock_drain_state();
+
+        if drain_state.is_draining {
+            return Err(shutting_down_error(&upload_description));
+        }
+
+        return Ok(self.register_upload(&mut drain_state, upload_description));
+    }
+
+    /**
+    registers the upload of a whole file as in flight, so that shutdown waits for all of it
+    rather than for one chunk at a time, its chunks being let in on the handle returned
+
+    # Errors
+
+    This function will return an error if draining has already started
+     */
+    pub fn start_file_upload(
+        &self,
+        upload_request_id: &String,
+        chunk_source: FileUploadChunkSource,
+    ) -> Result<InFlightUpload<'_>, ServiceError> {
+        let mut drain_state = self.lock_drain_state();
+        let upload_description = format!("file of upload {} ({:?})", upload_request_id, chunk_source);
+
+        if drain_state.is_draining {
+            return Err(shutting_down_error(&upload_description));
+        }
+
+        return Ok(self.register_upload(&mut drain_state, upload_description));
+    }
+
+    pub fn is_draining(&self) -> bool {
+        return self.lock_drain_state().is_draining;
+    }
+
+    pub fn in_flight_uploads(&self) -> Vec<String> {
+        let mut in_flight_uploads: Vec<String> = self
+            .lock_drain_state()
+            .in_flight_uploads
+            .values()
+            .cloned()
+            .collect();
+
+        in_flight_uploads.sort();
+
+        return in_flight_uploads;
+    }
+
+    /**
+    stops new uploads and waits up to the deadline for the in-flight ones to finish,
+    returning whatever was still in flight when it gave up
+     */
+    pub async fn drain(&self, deadline: Duration) -> Vec<String> {
+        self.lock_drain_state().is_draining = true;
+
+        let _ = tokio::time::timeout(deadline, async {
+            while !self.lock_drain_state().in_flight_uploads.is_empty() {
+                self.upload_finished.notified().await;
+            }
+        })
+        .await;
+
+        return self.in_flight_uploads();
+    }
+
+    fn register_upload(&self, drain_state: &mut DrainState, upload_description: String) -> InFlightUpload<'_> {
+        let upload_id = drain_state.next_upload_id;
+        drain_state.next_upload_id += 1;
+        drain_state
+            .in_flight_uploads
+            .insert(upload_id, upload_description);
+
+        return InFlightUpload {
+            upload_drain: self,
+            upload_id,
+        };
+    }
+
+    fn finish_upload(&self, upload_id: u64) {
+        let mut drain_state = self.lock_drain_state();
+        drain_state.in_flight_uploads.remove(&upload_id);
+        drop(drain_state);
+
+        //notify_one keeps the wake up for a drain that is not waiting yet
+        self.upload_finished.notify_one();
+    }
+
+    fn lock_drain_state(&self) -> MutexGuard<'_, DrainState> {
+        //the lock is never held across an await or a panic, so poisoning can be ignored
+        return self
+            .drain_state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+}
+
+/**
+a request turned away because the service is shutting down, it is safe to retry elsewhere
+ */
+fn shutting_down_error(upload_description: &str) -> ServiceError {
+    let error_msg = format!("not accepting {}, try again shortly", upload_description);
+
+    return ServiceError::new(ServiceErrorKind::ShuttingDown, error_msg);
+}
+
+impl Drop for InFlightUpload<'_> {
+    fn drop(&mut self) {
+        self.upload_drain.finish_upload(self.upload_id);
+    }
+}
+
+/**
+takes the instance out of rotation as soon as it starts draining
+ */
+pub struct UploadDrainHealthCheck {
+    pub upload_drain: Arc<UploadDrain>,
+}
+
+#[async_trait]
+impl DependencyHealthCheckInterface for UploadDrainHealthCheck {
+    fn dependency_name(&self) -> String {
+        return SHUTDOWN_DEPENDENCY_NAME.to_string();
+    }
+
+    async fn check_health(&self) -> Result<(), AppError> {
+        if self.upload_drain.is_draining() {
+            let error_msg = format!(
+                "shutting down, draining {} in-flight uploads",
+                self.upload_drain.in_flight_uploads().len()
+            );
+            return Err(AppError::new(AppErrorKind::ConnectionError, error_msg));
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::internal::{
+    interfaces::{
+        file_chunk_upload_service::{
+            FileChunkUploadServiceInterface, MockFileChunkUploadServiceInterface,
+        },
+        health_check::DependencyHealthCheckInterface,
+    },
+    models::{
+        entities::service_errors::ServiceErrorKind,
+        view_models::{
+            requests::upload_file_chunk_request::UploadFileChunkRequest,
+            responses::upload_file_chunk_response::UploadFileChunkResponse,
+        },
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        file_row::FileRow, file_upload_chunk::FileUploadChunkSource,
+    },
+};
+
+use super::draining_file_upload_service::DrainingFileChunkUploadService;
+use super::upload_drain::{UploadDrain, UploadDrainHealthCheck};
+
+#[actix_rt::test]
+async fn given_no_uploads_in_flight_drain_abandons_nothing() {
+    let sut = UploadDrain::new();
+
+    let actual = sut.drain(Duration::from_secs(5)).await;
+
+    assert!(actual.is_empty());
+    assert!(sut.is_draining());
+}
+
+#[actix_rt::test]
+async fn given_upload_finishes_before_the_deadline_drain_waits_for_it() {
+    let sut = UploadDrain::new();
+    let in_flight_upload = sut
+        .start_upload(String::from("chunk 1 of upload 1234"))
+        .unwrap();
+
+    let (actual, _) = tokio::join!(sut.drain(Duration::from_secs(5)), async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(in_flight_upload);
+    });
+
+    assert!(actual.is_empty());
+}
+
+#[actix_rt::test]
+async fn given_upload_outlives_the_deadline_drain_reports_it_abandoned() {
+    let sut = UploadDrain::new();
+    let _in_flight_upload = sut
+        .start_upload(String::from("chunk 1 of upload 1234"))
+        .unwrap();
+
+    let actual = sut.drain(Duration::from_millis(20)).await;
+
+    assert_eq!(actual, vec!["chunk 1 of upload 1234"]);
+}
+
+#[actix_rt::test]
+async fn given_draining_has_started_new_uploads_are_turned_away() {
+    let upload_drain = Arc::new(UploadDrain::new());
+    upload_drain.drain(Duration::ZERO).await;
+
+    //the inner service has no expectations, so reaching it would panic
+    let sut = DrainingFileChunkUploadService {
+        inner_service: Box::new(MockFileChunkUploadServiceInterface::new()),
+        upload_drain: upload_drain.clone(),
+    };
+
+    let actual = sut.upload_file_chunk(dummy_valid_test_request()).await;
+
+    assert_eq!(actual.unwrap_err().kind, ServiceErrorKind::ShuttingDown);
+}
+
+#[actix_rt::test]
+async fn given_file_upload_under_way_its_chunks_are_let_in_while_draining() {
+    let upload_drain = Arc::new(UploadDrain::new());
+    let in_flight_file = upload_drain
+        .start_file_upload(
+            &String::from("1234"),
+            FileUploadChunkSource::PrimaryFileChunk,
+        )
+        .unwrap();
+
+    let abandoned_uploads = upload_drain.drain(Duration::from_millis(20)).await;
+
+    let mut mock_service = MockFileChunkUploadServiceInterface::new();
+    mock_service
+        .expect_upload_file_chunk_of_file()
+        .times(1)
+        .returning(|_, _| {
+            Ok(UploadFileChunkResponse {
+                file_chunk_id: String::from("FILE-CHUNK-1"),
+                missing_chunk_sequence_numbers: None,
+            })
+        });
+
+    let sut = DrainingFileChunkUploadService {
+        inner_service: Box::new(mock_service),
+        upload_drain: upload_drain.clone(),
+    };
+
+    let actual = sut
+        .upload_file_chunk_of_file(dummy_valid_test_request(), &in_flight_file)
+        .await;
+
+    assert_eq!(
+        abandoned_uploads,
+        vec!["file of upload 1234 (PrimaryFileChunk)"]
+    );
+    assert!(actual.is_ok());
+}
+
+#[actix_rt::test]
+async fn given_file_upload_under_way_chunks_sent_on_their_own_for_it_are_turned_away_while_draining(
+) {
+    let upload_drain = Arc::new(UploadDrain::new());
+    let _in_flight_file = upload_drain
+        .start_file_upload(
+            &String::from("1234"),
+            FileUploadChunkSource::PrimaryFileChunk,
+        )
+        .unwrap();
+
+    upload_drain.drain(Duration::ZERO).await;
+
+    //the inner service has no expectations, so reaching it would panic
+    let sut = DrainingFileChunkUploadService {
+        inner_service: Box::new(MockFileChunkUploadServiceInterface::new()),
+        upload_drain: upload_drain.clone(),
+    };
+
+    let actual = sut.upload_file_chunk(dummy_valid_test_request()).await;
+
+    assert_eq!(actual.unwrap_err().kind, ServiceErrorKind::ShuttingDown);
+}
+
+#[actix_rt::test]
+async fn given_draining_has_started_readiness_check_fails() {
+    let upload_drain = Arc::new(UploadDrain::new());
+    let sut = UploadDrainHealthCheck {
+        upload_drain: upload_drain.clone(),
+    };
+
+    assert!(sut.check_health().await.is_ok());
+
+    upload_drain.drain(Duration::ZERO).await;
+
+    assert!(sut.check_health().await.is_err());
+}
+
+fn dummy_valid_test_request() -> UploadFileChunkRequest {
+    UploadFileChunkRequest {
+        upload_request_id: String::from("1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::PrimaryFileChunk,
+        chunk_rows: vec![FileRow {
+            raw_data: String::from("testing, 1234"),
+            row_number: 1,
+        }],
+        is_last_chunk: false,
+        file_hash: None,
+        is_file_hash_computed: false,
+    }
+}
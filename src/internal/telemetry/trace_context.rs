@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use actix_web::http::header::HeaderMap;
+use opentelemetry::{
+    propagation::{Extractor, TextMapPropagator},
+    sdk::propagation::TraceContextPropagator,
+    trace::TraceContextExt,
+    Context,
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+//dapr only takes the trace context of a published cloud event from these metadata keys
+const CLOUD_EVENT_METADATA_PREFIX: &'static str = "cloudevent.";
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl<'a> Extractor for HeaderExtractor<'a> {
+    fn get(&self, key: &str) -> Option<&str> {
+        return self.0.get(key).and_then(|value| value.to_str().ok());
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        return self.0.keys().map(|key| key.as_str()).collect();
+    }
+}
+
+/**
+reads the W3C traceparent and tracestate headers of an incoming request,
+an empty context is returned if there are none or they are malformed
+ */
+pub fn extract_trace_context(headers: &HeaderMap) -> Context {
+    return TraceContextPropagator::new().extract(&HeaderExtractor(headers));
+}
+
+/**
+the trace context as dapr publish metadata, so subscribers carry on the same trace
+ */
+pub fn trace_metadata_of(trace_context: &Context) -> HashMap<String, String> {
+    let mut trace_headers: HashMap<String, String> = HashMap::new();
+    TraceContextPropagator::new().inject_context(trace_context, &mut trace_headers);
+
+    let mut trace_metadata: HashMap<String, String> = HashMap::new();
+    for (key, value) in trace_headers {
+        trace_metadata.insert(
+            format!("{}{}", CLOUD_EVENT_METADATA_PREFIX, key),
+            value.clone(),
+        );
+        trace_metadata.insert(key, value);
+    }
+
+    return trace_metadata;
+}
+
+/**
+the dapr publish metadata for the span we are currently in, falling back to the current
+trace context when spans carry none because no tracing exporter is set
+ */
+pub fn current_trace_metadata() -> HashMap<String, String> {
+    let span_trace_context = tracing::Span::current().context();
+
+    if span_trace_context.span().span_context().is_valid() {
+        return trace_metadata_of(&span_trace_context);
+    }
+
+    return trace_metadata_of(&Context::current());
+}
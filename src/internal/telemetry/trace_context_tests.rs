@@ -0,0 +1,65 @@
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::trace::TraceContextExt;
+
+use super::trace_context::{current_trace_metadata, extract_trace_context, trace_metadata_of};
+
+const TEST_TRACEPARENT: &'static str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[test]
+fn test_extract_trace_context_reads_traceparent_header() {
+    let headers = headers_with_traceparent(TEST_TRACEPARENT);
+
+    let actual = extract_trace_context(&headers);
+
+    assert_eq!(
+        actual.span().span_context().trace_id().to_string(),
+        "4bf92f3577b34da6a3ce929d0e0e4736"
+    );
+}
+
+#[test]
+fn test_trace_metadata_of_propagates_incoming_trace_context() {
+    let trace_context = extract_trace_context(&headers_with_traceparent(TEST_TRACEPARENT));
+
+    let actual = trace_metadata_of(&trace_context);
+
+    assert_eq!(
+        actual.get("traceparent"),
+        Some(&TEST_TRACEPARENT.to_string())
+    );
+    assert_eq!(
+        actual.get("cloudevent.traceparent"),
+        Some(&TEST_TRACEPARENT.to_string())
+    );
+}
+
+#[test]
+fn test_trace_metadata_of_when_traceparent_is_malformed_is_empty() {
+    let trace_context = extract_trace_context(&headers_with_traceparent("not-a-traceparent"));
+
+    let actual = trace_metadata_of(&trace_context);
+
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn test_current_trace_metadata_without_tracing_exporter_propagates_current_trace_context() {
+    let trace_context = extract_trace_context(&headers_with_traceparent(TEST_TRACEPARENT));
+    let _trace_context_guard = trace_context.attach();
+
+    let actual = current_trace_metadata();
+
+    assert_eq!(
+        actual.get("traceparent"),
+        Some(&TEST_TRACEPARENT.to_string())
+    );
+}
+
+fn headers_with_traceparent(traceparent: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        HeaderName::from_static("traceparent"),
+        HeaderValue::from_static(traceparent),
+    );
+    return headers;
+}
@@ -0,0 +1,128 @@
+use opentelemetry::{
+    global,
+    sdk::{
+        export::trace::stdout,
+        trace::{self, Tracer},
+        Resource,
+    },
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use serde::Serialize;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::internal::shared_reconciler_rust_libraries::common::utils::app_error_with_msg;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+const OTLP_TRACING_EXPORTER: &'static str = "otlp";
+const STDOUT_TRACING_EXPORTER: &'static str = "stdout";
+const NO_TRACING_EXPORTER: &'static str = "none";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TracingSettings {
+    //one of otlp, stdout or none
+    pub tracing_exporter: String,
+
+    pub otlp_endpoint: String,
+
+    pub service_name: String,
+
+    //which logs and spans to keep, in RUST_LOG syntax e.g. "info"
+    pub log_filter: String,
+}
+
+/**
+writes logs as json lines to stdout and sends spans to the configured exporter,
+the stdout exporter being meant for local runs and writing to stderr, so that its
+spans dont get mixed up with the log lines
+
+# Errors
+
+This function will return an error if the log filter is invalid or the exporter is unknown
+or cant be set up
+ */
+pub fn init_tracing(tracing_settings: &TracingSettings) -> Result<(), AppError> {
+    let log_filter = match EnvFilter::try_new(&tracing_settings.log_filter) {
+        Ok(log_filter) => log_filter,
+        Err(e) => {
+            let error_msg = format!(
+                "invalid log filter [{}]: {}",
+                tracing_settings.log_filter, e
+            );
+            return app_error_with_msg(AppErrorKind::InternalError, &error_msg);
+        }
+    };
+
+    let tracer = match tracing_settings.tracing_exporter.to_lowercase().as_str() {
+        NO_TRACING_EXPORTER => None,
+        STDOUT_TRACING_EXPORTER => Some(
+            stdout::new_pipeline()
+                .with_writer(std::io::stderr())
+                .with_trace_config(trace_config(tracing_settings))
+                .install_simple(),
+        ),
+        OTLP_TRACING_EXPORTER => Some(install_otlp_tracer(tracing_settings)?),
+        unknown_exporter => {
+            let error_msg = format!(
+                "unknown tracing exporter [{}], expected one of {}, {} or {}",
+                unknown_exporter,
+                OTLP_TRACING_EXPORTER,
+                STDOUT_TRACING_EXPORTER,
+                NO_TRACING_EXPORTER
+            );
+            return app_error_with_msg(AppErrorKind::InternalError, &error_msg);
+        }
+    };
+
+    let tracing_layer = tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer));
+
+    //the fields of the span we are in (e.g. the correlation id) go out with every log line
+    let log_layer = tracing_subscriber::fmt::layer()
+        .json()
+        .with_current_span(true)
+        .with_span_list(false);
+
+    let init_result = tracing_subscriber::registry()
+        .with(log_filter)
+        .with(log_layer)
+        .with(tracing_layer)
+        .try_init();
+
+    return match init_result {
+        Ok(_) => Ok(()),
+        Err(e) => Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+    };
+}
+
+/**
+flushes the spans that have not been exported yet
+ */
+pub fn shutdown_tracing() {
+    global::shutdown_tracer_provider();
+}
+
+fn install_otlp_tracer(tracing_settings: &TracingSettings) -> Result<Tracer, AppError> {
+    let install_result = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(tracing_settings.otlp_endpoint.clone()),
+        )
+        .with_trace_config(trace_config(tracing_settings))
+        .install_batch(opentelemetry::runtime::Tokio);
+
+    return match install_result {
+        Ok(tracer) => Ok(tracer),
+        Err(e) => Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+    };
+}
+
+fn trace_config(tracing_settings: &TracingSettings) -> trace::Config {
+    return trace::config().with_resource(Resource::new(vec![KeyValue::new(
+        "service.name",
+        tracing_settings.service_name.clone(),
+    )]));
+}
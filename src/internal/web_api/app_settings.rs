@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+use reqwest::Url;
+use serde::Serialize;
+use tracing_subscriber::EnvFilter;
+
+use crate::internal::{
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+    telemetry::tracing_setup::TracingSettings,
+};
+
+const CONFIG_FILE_KEY: &'static str = "config";
+const CONFIG_FILE_ENV_VAR: &'static str = "APP_CONFIG_FILE";
+const CLI_FLAG_PREFIX: &'static str = "--";
+const REDACTED_VALUE: &'static str = "***";
+const TRACING_EXPORTERS: [&'static str; 3] = ["otlp", "stdout", "none"];
+
+/**
+a setting and where it can come from
+
+later sources override earlier ones: the default, the TOML config file (under the key),
+the env var and finally the CLI flag (the key with - in place of _, e.g. --app-port)
+ */
+struct SettingSource {
+    key: &'static str,
+    env_var: &'static str,
+    default_value: Option<&'static str>,
+}
+
+const SETTING_SOURCES: [SettingSource; 22] = [
+    SettingSource {
+        key: "app_ip",
+        env_var: "APP_IP",
+        default_value: Some("0.0.0.0"),
+    },
+    SettingSource {
+        key: "app_port",
+        env_var: "APP_PORT",
+        default_value: Some("8084"),
+    },
+    SettingSource {
+        key: "dapr_pubsub_name",
+        env_var: "DAPR_PUBSUB_NAME",
+        default_value: Some("pubsub"),
+    },
+    SettingSource {
+        key: "dapr_pubsub_server_address",
+        env_var: "DAPR_IP",
+        default_value: Some("http://localhost:5006"),
+    },
+    SettingSource {
+        key: "dapr_state_store_name",
+        env_var: "DAPR_STATE_STORE_NAME",
+        default_value: Some("statestore"),
+    },
+    SettingSource {
+        key: "file_upload_completed_topic",
+        env_var: "FILE_UPLOAD_COMPLETED_TOPIC",
+        default_value: None,
+    },
+    SettingSource {
+        key: "file_upload_verification_topic",
+        env_var: "FILE_UPLOAD_VERIFICATION_TOPIC",
+        default_value: Some("file-upload-verifications"),
+    },
+    SettingSource {
+        key: "recon_tasks_service_name",
+        env_var: "RECON_TASKS_SERVICE_NAME",
+        default_value: Some("svc-task-details-repository-manager"),
+    },
+    SettingSource {
+        key: "recon_tasks_connection_url",
+        env_var: "RECON_TASKS_SERVICE_HOST",
+        default_value: Some("http://localhost:3600"),
+    },
+    SettingSource {
+        key: "pubsub_retry_max_attempts",
+        env_var: "PUBSUB_RETRY_MAX_ATTEMPTS",
+        default_value: Some("4"),
+    },
+    SettingSource {
+        key: "pubsub_retry_base_delay_millis",
+        env_var: "PUBSUB_RETRY_BASE_DELAY_MILLIS",
+        default_value: Some("100"),
+    },
+    SettingSource {
+        key: "pubsub_retry_max_delay_millis",
+        env_var: "PUBSUB_RETRY_MAX_DELAY_MILLIS",
+        default_value: Some("2000"),
+    },
+    SettingSource {
+        key: "pubsub_retry_jitter",
+        env_var: "PUBSUB_RETRY_JITTER",
+        default_value: Some("0.5"),
+    },
+    SettingSource {
+        key: "recon_tasks_cache_ttl_seconds",
+        env_var: "RECON_TASKS_CACHE_TTL_SECONDS",
+        default_value: Some("60"),
+    },
+    SettingSource {
+        key: "recon_tasks_cache_max_entries",
+        env_var: "RECON_TASKS_CACHE_MAX_ENTRIES",
+        default_value: Some("1000"),
+    },
+    SettingSource {
+        key: "recon_tasks_health_check_path",
+        env_var: "RECON_TASKS_HEALTH_CHECK_PATH",
+        default_value: Some("health"),
+    },
+    SettingSource {
+        key: "health_check_timeout_millis",
+        env_var: "HEALTH_CHECK_TIMEOUT_MILLIS",
+        default_value: Some("2000"),
+    },
+    SettingSource {
+        key: "shutdown_drain_deadline_millis",
+        env_var: "SHUTDOWN_DRAIN_DEADLINE_MILLIS",
+        default_value: Some("25000"),
+    },
+    SettingSource {
+        key: "tracing_exporter",
+        env_var: "TRACING_EXPORTER",
+        default_value: Some("none"),
+    },
+    SettingSource {
+        key: "otlp_endpoint",
+        env_var: "OTEL_EXPORTER_OTLP_ENDPOINT",
+        default_value: Some("http://localhost:4317"),
+    },
+    SettingSource {
+        key: "service_name",
+        env_var: "OTEL_SERVICE_NAME",
+        default_value: Some("svc-file-chunks-upload-manager"),
+    },
+    SettingSource {
+        key: "log_filter",
+        env_var: "RUST_LOG",
+        default_value: Some("info"),
+    },
+];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AppSettings {
+    pub app_ip: IpAddr,
+
+    pub app_port: u16,
+
+    pub dapr_pubsub_name: String,
+
+    pub dapr_pubsub_server_address: String,
+
+    pub dapr_state_store_name: String,
+
+    pub file_upload_completed_topic: Option<String>,
+
+    pub file_upload_verification_topic: String,
+
+    pub recon_tasks_service_name: String,
+
+    pub recon_tasks_connection_url: String,
+
+    pub pubsub_retry_max_attempts: u32,
+
+    pub pubsub_retry_base_delay_millis: u64,
+
+    pub pubsub_retry_max_delay_millis: u64,
+
+    pub pubsub_retry_jitter: f64,
+
+    pub recon_tasks_cache_ttl_seconds: u64,
+
+    pub recon_tasks_cache_max_entries: usize,
+
+    pub recon_tasks_health_check_path: String,
+
+    pub health_check_timeout_millis: u64,
+
+    //how long in-flight uploads get to finish on shutdown before they are abandoned
+    pub shutdown_drain_deadline_millis: u64,
+
+    pub tracing_settings: TracingSettings,
+}
+
+impl AppSettings {
+    /**
+    a copy that is safe to print, with the passwords in any of the urls masked
+     */
+    pub fn redacted(&self) -> AppSettings {
+        let mut redacted_settings = self.clone();
+
+        redacted_settings.dapr_pubsub_server_address = redact_url(&self.dapr_pubsub_server_address);
+        redacted_settings.recon_tasks_connection_url = redact_url(&self.recon_tasks_connection_url);
+        redacted_settings.tracing_settings.otlp_endpoint =
+            redact_url(&self.tracing_settings.otlp_endpoint);
+
+        return redacted_settings;
+    }
+}
+
+/**
+reads the settings from the process env vars and CLI args, and the config file they point at
+
+# Errors
+
+This function will return an error listing every setting that is missing or invalid
+ */
+pub fn load_app_settings() -> Result<AppSettings, AppError> {
+    let env_vars: HashMap<String, String> = std::env::vars().collect();
+    let cli_args: Vec<String> = std::env::args().skip(1).collect();
+
+    return load_app_settings_from(&env_vars, &cli_args);
+}
+
+/**
+layers the defaults, the config file (named by --config or APP_CONFIG_FILE), the env vars
+and the CLI flags, then parses and validates the result
+
+# Errors
+
+This function will return an error listing every setting that is missing or invalid
+ */
+pub fn load_app_settings_from(
+    env_vars: &HashMap<String, String>,
+    cli_args: &[String],
+) -> Result<AppSettings, AppError> {
+    let mut problems: Vec<String> = vec![];
+    let mut raw_settings: HashMap<&'static str, RawSetting> = HashMap::new();
+
+    let cli_flags = parse_cli_flags(cli_args, &mut problems);
+
+    for setting_source in SETTING_SOURCES.iter() {
+        if let Some(default_value) = setting_source.default_value {
+            raw_settings.insert(
+                setting_source.key,
+                RawSetting::new(default_value.to_string(), "default".to_string()),
+            );
+        }
+    }
+
+    let config_file_path = cli_flags
+        .get(CONFIG_FILE_KEY)
+        .or_else(|| env_vars.get(CONFIG_FILE_ENV_VAR));
+
+    if let Some(config_file_path) = config_file_path {
+        for (key, value) in read_config_file(config_file_path, &mut problems) {
+            let origin = format!("config file {}", config_file_path);
+            raw_settings.insert(key, RawSetting::new(value, origin));
+        }
+    }
+
+    for setting_source in SETTING_SOURCES.iter() {
+        if let Some(value) = env_vars.get(setting_source.env_var) {
+            let origin = format!("env var {}", setting_source.env_var);
+            raw_settings.insert(setting_source.key, RawSetting::new(value.clone(), origin));
+        }
+
+        if let Some(value) = cli_flags.get(setting_source.key) {
+            let origin = format!("flag {}", to_cli_flag(setting_source.key));
+            raw_settings.insert(setting_source.key, RawSetting::new(value.clone(), origin));
+        }
+    }
+
+    let mut settings_parser = SettingsParser {
+        raw_settings,
+        problems,
+    };
+
+    let app_settings = settings_parser.parse_app_settings();
+
+    if settings_parser.problems.is_empty() {
+        return Ok(app_settings);
+    }
+
+    let error_msg = format!(
+        "invalid configuration:\n - {}",
+        settings_parser.problems.join("\n - ")
+    );
+    return Err(AppError::new(AppErrorKind::InternalError, error_msg));
+}
+
+struct RawSetting {
+    value: String,
+
+    //where the value came from, so a bad one can be tracked down
+    origin: String,
+}
+
+impl RawSetting {
+    fn new(value: String, origin: String) -> RawSetting {
+        return RawSetting { value, origin };
+    }
+}
+
+/**
+turns the raw settings into typed ones, collecting every problem instead of stopping at the first
+ */
+struct SettingsParser {
+    raw_settings: HashMap<&'static str, RawSetting>,
+    problems: Vec<String>,
+}
+
+impl SettingsParser {
+    fn parse_app_settings(&mut self) -> AppSettings {
+        let app_settings = AppSettings {
+            app_ip: self.ip("app_ip"),
+            app_port: self.number("app_port", 1, u16::MAX),
+            dapr_pubsub_name: self.name("dapr_pubsub_name"),
+            dapr_pubsub_server_address: self.url("dapr_pubsub_server_address"),
+            dapr_state_store_name: self.name("dapr_state_store_name"),
+            file_upload_completed_topic: self.optional_name("file_upload_completed_topic"),
+            file_upload_verification_topic: self.name("file_upload_verification_topic"),
+            recon_tasks_service_name: self.name("recon_tasks_service_name"),
+            recon_tasks_connection_url: self.url("recon_tasks_connection_url"),
+            pubsub_retry_max_attempts: self.number("pubsub_retry_max_attempts", 1, 100),
+            pubsub_retry_base_delay_millis: self.number(
+                "pubsub_retry_base_delay_millis",
+                1,
+                u64::MAX,
+            ),
+            pubsub_retry_max_delay_millis: self.number(
+                "pubsub_retry_max_delay_millis",
+                1,
+                u64::MAX,
+            ),
+            pubsub_retry_jitter: self.number("pubsub_retry_jitter", 0.0, 1.0),
+            recon_tasks_cache_ttl_seconds: self.number(
+                "recon_tasks_cache_ttl_seconds",
+                0,
+                u64::MAX,
+            ),
+            recon_tasks_cache_max_entries: self.number(
+                "recon_tasks_cache_max_entries",
+                1,
+                usize::MAX,
+            ),
+            recon_tasks_health_check_path: self.text("recon_tasks_health_check_path"),
+            health_check_timeout_millis: self.number("health_check_timeout_millis", 1, u64::MAX),
+            shutdown_drain_deadline_millis: self.number(
+                "shutdown_drain_deadline_millis",
+                0,
+                u64::MAX,
+            ),
+            tracing_settings: TracingSettings {
+                tracing_exporter: self.one_of("tracing_exporter", &TRACING_EXPORTERS),
+                otlp_endpoint: self.url("otlp_endpoint"),
+                service_name: self.name("service_name"),
+                log_filter: self.log_filter("log_filter"),
+            },
+        };
+
+        if app_settings.pubsub_retry_base_delay_millis > app_settings.pubsub_retry_max_delay_millis
+        {
+            self.problems.push(format!(
+                "pubsub_retry_base_delay_millis ({}) cant be more than pubsub_retry_max_delay_millis ({})",
+                app_settings.pubsub_retry_base_delay_millis,
+                app_settings.pubsub_retry_max_delay_millis
+            ));
+        }
+
+        return app_settings;
+    }
+
+    fn text(&mut self, key: &'static str) -> String {
+        return match self.optional_text(key) {
+            Some(value) => value,
+            None => {
+                self.problems.push(format!("{} is not set", key));
+                String::new()
+            }
+        };
+    }
+
+    fn optional_text(&mut self, key: &'static str) -> Option<String> {
+        return self
+            .raw_settings
+            .get(key)
+            .map(|raw_setting| raw_setting.value.trim().to_string())
+            .filter(|value| !value.is_empty());
+    }
+
+    fn name(&mut self, key: &'static str) -> String {
+        let value = self.text(key);
+        if !value.is_empty() {
+            self.check(
+                key,
+                is_valid_name(&value),
+                "is not a valid name, use only letters, digits, - _ and .",
+            );
+        }
+        return value;
+    }
+
+    fn optional_name(&mut self, key: &'static str) -> Option<String> {
+        let value = self.optional_text(key)?;
+        self.check(
+            key,
+            is_valid_name(&value),
+            "is not a valid name, use only letters, digits, - _ and .",
+        );
+        return Some(value);
+    }
+
+    fn url(&mut self, key: &'static str) -> String {
+        let value = self.text(key);
+        if !value.is_empty() {
+            self.check(key, is_valid_http_url(&value), "is not a valid http(s) url");
+        }
+        return value;
+    }
+
+    fn ip(&mut self, key: &'static str) -> IpAddr {
+        let value = self.text(key);
+        return match IpAddr::from_str(&value) {
+            Ok(ip) => ip,
+            Err(_) => {
+                if !value.is_empty() {
+                    self.check(key, false, "is not a valid ip address");
+                }
+                IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+            }
+        };
+    }
+
+    fn number<T>(&mut self, key: &'static str, min: T, max: T) -> T
+    where
+        T: FromStr + PartialOrd + Display + Copy,
+    {
+        let value = self.text(key);
+        return match T::from_str(&value) {
+            Ok(number) if number >= min && number <= max => number,
+            _ => {
+                if !value.is_empty() {
+                    let reason = format!("is not a number from {} to {}", min, max);
+                    self.check(key, false, &reason);
+                }
+                min
+            }
+        };
+    }
+
+    fn one_of(&mut self, key: &'static str, options: &[&str]) -> String {
+        let value = self.text(key).to_lowercase();
+        if !value.is_empty() {
+            let reason = format!("is not one of {}", options.join(", "));
+            self.check(key, options.contains(&value.as_str()), &reason);
+        }
+        return value;
+    }
+
+    fn log_filter(&mut self, key: &'static str) -> String {
+        let value = self.text(key);
+        if !value.is_empty() {
+            self.check(
+                key,
+                EnvFilter::try_new(&value).is_ok(),
+                "is not a valid log filter",
+            );
+        }
+        return value;
+    }
+
+    fn check(&mut self, key: &'static str, is_valid: bool, reason: &str) {
+        if is_valid {
+            return;
+        }
+
+        //the value is echoed back, so a password in it is masked like in the effective config
+        let problem = match self.raw_settings.get(key) {
+            None => format!("{} {}", key, reason),
+            Some(raw_setting) => format!(
+                "{} = '{}' (from {}) {}",
+                key,
+                redact_url(&raw_setting.value),
+                raw_setting.origin,
+                reason
+            ),
+        };
+        self.problems.push(problem);
+    }
+}
+
+fn parse_cli_flags(cli_args: &[String], problems: &mut Vec<String>) -> HashMap<String, String> {
+    let mut cli_flags: HashMap<String, String> = HashMap::new();
+    let mut cli_args = cli_args.iter();
+
+    while let Some(cli_arg) = cli_args.next() {
+        let flag = match cli_arg.strip_prefix(CLI_FLAG_PREFIX) {
+            Some(flag) => flag,
+            None => {
+                problems.push(format!("unexpected argument '{}'", cli_arg));
+                continue;
+            }
+        };
+
+        //both --app-port 8084 and --app-port=8084 are accepted
+        let (flag_name, value) = match flag.split_once('=') {
+            Some((flag_name, value)) => (flag_name, Some(value.to_string())),
+            None => (flag, cli_args.next().cloned()),
+        };
+
+        let key = flag_name.replace('-', "_");
+        if !is_known_key(&key) && key != CONFIG_FILE_KEY {
+            problems.push(format!("unknown flag {}{}", CLI_FLAG_PREFIX, flag_name));
+            continue;
+        }
+
+        match value {
+            Some(value) => {
+                cli_flags.insert(key, value);
+            }
+            None => problems.push(format!(
+                "flag {}{} needs a value",
+                CLI_FLAG_PREFIX, flag_name
+            )),
+        }
+    }
+
+    return cli_flags;
+}
+
+fn read_config_file(
+    config_file_path: &String,
+    problems: &mut Vec<String>,
+) -> Vec<(&'static str, String)> {
+    let config_file_contents = match std::fs::read_to_string(config_file_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            problems.push(format!("cant read config file {}: {}", config_file_path, e));
+            return vec![];
+        }
+    };
+
+    let config_table = match toml::from_str::<toml::value::Table>(&config_file_contents) {
+        Ok(config_table) => config_table,
+        Err(e) => {
+            problems.push(format!(
+                "config file {} is not valid TOML: {}",
+                config_file_path, e
+            ));
+            return vec![];
+        }
+    };
+
+    let mut config_file_settings: Vec<(&'static str, String)> = vec![];
+
+    for (key, value) in config_table {
+        let setting_source = match SETTING_SOURCES.iter().find(|source| source.key == key) {
+            Some(setting_source) => setting_source,
+            None => {
+                problems.push(format!(
+                    "config file {} has unknown setting {}",
+                    config_file_path, key
+                ));
+                continue;
+            }
+        };
+
+        let value = match value {
+            toml::Value::String(value) => value,
+            toml::Value::Integer(value) => value.to_string(),
+            toml::Value::Float(value) => value.to_string(),
+            toml::Value::Boolean(value) => value.to_string(),
+            _ => {
+                problems.push(format!(
+                    "config file {} setting {} must be a plain value",
+                    config_file_path, key
+                ));
+                continue;
+            }
+        };
+
+        config_file_settings.push((setting_source.key, value));
+    }
+
+    return config_file_settings;
+}
+
+fn is_known_key(key: &str) -> bool {
+    return SETTING_SOURCES
+        .iter()
+        .any(|setting_source| setting_source.key == key);
+}
+
+fn to_cli_flag(key: &str) -> String {
+    return format!("{}{}", CLI_FLAG_PREFIX, key.replace('_', "-"));
+}
+
+fn is_valid_name(value: &str) -> bool {
+    return value
+        .chars()
+        .all(|character| character.is_ascii_alphanumeric() || "-_.".contains(character));
+}
+
+fn is_valid_http_url(value: &str) -> bool {
+    return match Url::parse(value) {
+        Ok(url) => (url.scheme() == "http" || url.scheme() == "https") && url.host().is_some(),
+        Err(_) => false,
+    };
+}
+
+fn redact_url(value: &str) -> String {
+    let mut url = match Url::parse(value) {
+        Ok(url) => url,
+        Err(_) => return value.to_string(),
+    };
+
+    if url.password().is_some() && url.set_password(Some(REDACTED_VALUE)).is_ok() {
+        return url.to_string();
+    }
+
+    return value.to_string();
+}
@@ -0,0 +1,47 @@
+use std::future::Future;
+use std::str::FromStr;
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    HttpMessage,
+};
+
+use crate::internal::web_api::utils::{correlation_id_of, CORRELATION_ID_HEADER};
+
+/**
+the correlation id of the request being handled, kept in the request extensions
+ */
+#[derive(Clone, Debug)]
+pub struct CorrelationId(pub String);
+
+/**
+gives every request a correlation id, the caller's own if it sent a usable one,
+and echoes it back on the response
+ */
+pub fn assign_correlation_id<S, B>(
+    request: ServiceRequest,
+    service: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, actix_web::Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+{
+    let correlation_id = correlation_id_of(request.request());
+    request
+        .extensions_mut()
+        .insert(CorrelationId(correlation_id.clone()));
+
+    let response_future = service.call(request);
+
+    return async move {
+        let mut response = response_future.await?;
+
+        let correlation_id_header = HeaderName::from_str(CORRELATION_ID_HEADER);
+        let correlation_id_value = HeaderValue::from_str(&correlation_id);
+        if let (Ok(header_name), Ok(header_value)) = (correlation_id_header, correlation_id_value) {
+            response.headers_mut().insert(header_name, header_value);
+        }
+
+        return Ok(response);
+    };
+}
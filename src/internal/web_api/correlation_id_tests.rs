@@ -0,0 +1,88 @@
+use actix_web::{
+    get,
+    test::{self, TestRequest},
+    App, HttpRequest, HttpResponse,
+};
+
+use crate::internal::web_api::{
+    correlation_id::assign_correlation_id,
+    utils::{correlation_id_of, CORRELATION_ID_HEADER},
+};
+
+#[get("/echo-correlation-id")]
+async fn echo_correlation_id(request: HttpRequest) -> HttpResponse {
+    return HttpResponse::Ok().body(correlation_id_of(&request));
+}
+
+#[actix_web::test]
+async fn test_assign_correlation_id_propagates_the_callers_id() {
+    let mut app = test::init_service(
+        App::new()
+            .wrap_fn(assign_correlation_id)
+            .service(echo_correlation_id),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri("/echo-correlation-id")
+        .insert_header((CORRELATION_ID_HEADER, "TEST-CORRELATION-1"))
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(
+        resp.headers().get(CORRELATION_ID_HEADER).unwrap(),
+        "TEST-CORRELATION-1"
+    );
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "TEST-CORRELATION-1");
+}
+
+#[actix_web::test]
+async fn test_assign_correlation_id_when_caller_sends_none_generates_one() {
+    let mut app = test::init_service(
+        App::new()
+            .wrap_fn(assign_correlation_id)
+            .service(echo_correlation_id),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri("/echo-correlation-id")
+        .send_request(&mut app)
+        .await;
+
+    let echoed_correlation_id = resp
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    //the handler sees the same id that goes back to the caller
+    let body = test::read_body(resp).await;
+    assert!(!echoed_correlation_id.is_empty());
+    assert_eq!(body, echoed_correlation_id.as_bytes());
+}
+
+#[actix_web::test]
+async fn test_assign_correlation_id_replaces_unusable_ids() {
+    let mut app = test::init_service(
+        App::new()
+            .wrap_fn(assign_correlation_id)
+            .service(echo_correlation_id),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri("/echo-correlation-id")
+        .insert_header((CORRELATION_ID_HEADER, "has spaces\" and quotes"))
+        .send_request(&mut app)
+        .await;
+
+    assert_ne!(
+        resp.headers().get(CORRELATION_ID_HEADER).unwrap(),
+        "has spaces\" and quotes"
+    );
+}
@@ -1,21 +1,228 @@
 use actix_web::{
-    HttpResponse,
+    delete, get, HttpRequest, HttpResponse,
     post,
     web::{self, Data},
 };
+use futures_util::StreamExt;
+use validator::Validate;
 
 use crate::internal::{
-    interfaces::file_chunk_upload_service::FileChunkUploadServiceInterface,
-    models::view_models::requests::upload_file_chunk_request::UploadFileChunkRequest,
+    interfaces::{
+        file_chunk_upload_service::FileChunkUploadServiceInterface,
+        health_check::HealthServiceInterface,
+    },
+    metrics::app_metrics::{gather_metrics, record_validation_rejection},
+    models::{
+        entities::service_errors::{ServiceError, ServiceErrorKind},
+        view_models::{
+            requests::{
+                abort_upload_request::AbortUploadRequest,
+                upload_file_chunk_request::UploadFileChunkRequest,
+                upload_file_request::UploadFileRequest,
+            },
+            responses::{
+                health_response::{HealthResponse, HealthStatus},
+                upload_file_response::UploadFileResponse,
+            },
+        },
+    },
+    services::{
+        core_logic::file_chunker::FileChunker,
+        upload_drain::{InFlightUpload, UploadDrain},
+    },
+    web_api::utils::{log_problem, ok_or_logged_problem, ok_or_problem, problem_response},
 };
-use crate::internal::shared_reconciler_rust_libraries::web_api::utils::ok_or_error;
+
+const DEFAULT_UPLOAD_FILE_CHUNK_SIZE: usize = 1000;
+const PROMETHEUS_TEXT_CONTENT_TYPE: &'static str = "text/plain; version=0.0.4";
 
 #[post("/upload-file-chunk")]
 pub(crate) async fn upload_file_chunk(
     task_details: web::Json<UploadFileChunkRequest>,
     service: Data<Box<dyn FileChunkUploadServiceInterface>>,
+    request: HttpRequest,
 ) -> HttpResponse {
     let recon_task_details = service.upload_file_chunk(task_details.0).await;
 
-    return ok_or_error(recon_task_details);
+    //the service logs every chunk it is sent, along with what became of it
+    return ok_or_logged_problem(&request, recon_task_details);
+}
+
+#[post("/upload-file-chunks")]
+pub(crate) async fn upload_file_chunks(
+    file_chunks: web::Json<Vec<UploadFileChunkRequest>>,
+    service: Data<Box<dyn FileChunkUploadServiceInterface>>,
+    request: HttpRequest,
+) -> HttpResponse {
+    let upload_file_chunks_result = service.upload_file_chunks(file_chunks.into_inner()).await;
+
+    return ok_or_problem(&request, upload_file_chunks_result);
+}
+
+#[post("/upload-file")]
+pub(crate) async fn upload_file(
+    upload_file_request: web::Query<UploadFileRequest>,
+    file_contents: web::Payload,
+    service: Data<Box<dyn FileChunkUploadServiceInterface>>,
+    upload_drain: Data<UploadDrain>,
+    request: HttpRequest,
+) -> HttpResponse {
+    //the whole file counts as one upload, so a shutdown neither cuts it off between chunks
+    //nor turns its remaining chunks away, which are let in on this handle alone
+    let in_flight_file = match upload_drain.start_file_upload(
+        &upload_file_request.upload_request_id,
+        upload_file_request.chunk_source,
+    ) {
+        Ok(in_flight_upload) => in_flight_upload,
+        Err(e) => return problem_response(&request, &e),
+    };
+
+    let upload_file_result = upload_file_in_chunks(
+        upload_file_request.0,
+        file_contents,
+        service.get_ref(),
+        &in_flight_file,
+    )
+    .await;
+
+    //a failed chunk was logged by the service, any other failure by upload_file_in_chunks
+    return ok_or_logged_problem(&request, upload_file_result);
+}
+
+#[get("/uploads/{upload_request_id}")]
+pub(crate) async fn get_upload_status(
+    upload_request_id: web::Path<String>,
+    service: Data<Box<dyn FileChunkUploadServiceInterface>>,
+    request: HttpRequest,
+) -> HttpResponse {
+    let upload_status = service.get_upload_status(upload_request_id.into_inner()).await;
+
+    return ok_or_problem(&request, upload_status);
+}
+
+#[delete("/uploads/{upload_request_id}")]
+pub(crate) async fn abort_upload(
+    upload_request_id: web::Path<String>,
+    abort_upload_request: web::Query<AbortUploadRequest>,
+    service: Data<Box<dyn FileChunkUploadServiceInterface>>,
+    request: HttpRequest,
+) -> HttpResponse {
+    let abort_upload_result = service
+        .abort_upload(upload_request_id.into_inner(), abort_upload_request.source)
+        .await;
+
+    return ok_or_problem(&request, abort_upload_result);
+}
+
+#[get("/health/live")]
+pub(crate) async fn check_liveness() -> HttpResponse {
+    //answering at all is proof enough that the process is alive
+    return HttpResponse::Ok().json(HealthResponse {
+        status: HealthStatus::Healthy,
+        dependencies: vec![],
+    });
+}
+
+#[get("/health/ready")]
+pub(crate) async fn check_readiness(
+    health_service: Data<Box<dyn HealthServiceInterface>>,
+) -> HttpResponse {
+    let readiness = health_service.check_readiness().await;
+
+    return match readiness.status {
+        HealthStatus::Healthy => HttpResponse::Ok().json(readiness),
+        HealthStatus::Unhealthy => HttpResponse::ServiceUnavailable().json(readiness),
+    };
+}
+
+#[get("/metrics")]
+pub(crate) async fn get_metrics(request: HttpRequest) -> HttpResponse {
+    return match gather_metrics() {
+        Ok(encoded_metrics) => HttpResponse::Ok()
+            .content_type(PROMETHEUS_TEXT_CONTENT_TYPE)
+            .body(encoded_metrics),
+        Err(e) => problem_response(&request, &ServiceError::from(e)),
+    };
+}
+
+/**
+streams the raw file line by line and uploads it a chunk at a time,
+so the whole file is never held in memory
+
+# Errors
+
+This function will return an error if the request fails validation, the file cant be read
+or any of its chunks fails to be uploaded
+ */
+async fn upload_file_in_chunks(
+    upload_file_request: UploadFileRequest,
+    mut file_contents: web::Payload,
+    service: &Box<dyn FileChunkUploadServiceInterface>,
+    in_flight_file: &InFlightUpload<'_>,
+) -> Result<UploadFileResponse, ServiceError> {
+    //validate request
+    match upload_file_request.validate() {
+        Ok(_) => (),
+        Err(e) => {
+            let service_error = ServiceError::validation_error(&e);
+            record_validation_rejection(&service_error);
+            return Err(logged(service_error));
+        }
+    }
+
+    let mut file_chunker = FileChunker::new(
+        upload_file_request.upload_request_id.clone(),
+        upload_file_request.chunk_source,
+        upload_file_request
+            .chunk_size
+            .unwrap_or(DEFAULT_UPLOAD_FILE_CHUNK_SIZE),
+        upload_file_request.has_header_row.unwrap_or(false),
+        upload_file_request
+            .column_delimiters
+            .as_ref()
+            .map(|column_delimiters| column_delimiters.chars().collect())
+            .unwrap_or_default(),
+    );
+
+    let mut file_chunk_ids: Vec<String> = vec![];
+
+    while let Some(file_bytes_result) = file_contents.next().await {
+        let file_bytes = match file_bytes_result {
+            Ok(file_bytes) => file_bytes,
+            Err(e) => {
+                let error_msg = format!("unable to read uploaded file: {}", e);
+                return Err(logged(ServiceError::new(
+                    ServiceErrorKind::BadClientRequest,
+                    error_msg,
+                )));
+            }
+        };
+
+        for file_chunk in file_chunker.push_bytes(&file_bytes).map_err(logged)? {
+            let upload_file_chunk_response = service
+                .upload_file_chunk_of_file(file_chunk, in_flight_file)
+                .await?;
+            file_chunk_ids.push(upload_file_chunk_response.file_chunk_id);
+        }
+    }
+
+    for file_chunk in file_chunker.finish().map_err(logged)? {
+        let upload_file_chunk_response = service
+            .upload_file_chunk_of_file(file_chunk, in_flight_file)
+            .await?;
+        file_chunk_ids.push(upload_file_chunk_response.file_chunk_id);
+    }
+
+    return Ok(UploadFileResponse {
+        upload_request_id: upload_file_request.upload_request_id,
+        chunk_source: upload_file_request.chunk_source,
+        total_rows: file_chunker.total_rows(),
+        file_chunk_ids,
+    });
+}
+
+//errors of the file itself, unlike those of its chunks, are not logged by the service
+fn logged(service_error: ServiceError) -> ServiceError {
+    log_problem(&service_error);
+    return service_error;
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use actix_web::{
     App,
     test::{self, TestRequest},
@@ -5,18 +7,39 @@ use actix_web::{
 };
 
 use crate::internal::{
-    interfaces::file_chunk_upload_service::{
-        FileChunkUploadServiceInterface, MockFileChunkUploadServiceInterface,
+    interfaces::{
+        file_chunk_upload_service::{
+            FileChunkUploadServiceInterface, MockFileChunkUploadServiceInterface,
+        },
+        health_check::{HealthServiceInterface, MockHealthServiceInterface},
     },
-    models::view_models::{
-        requests::upload_file_chunk_request::UploadFileChunkRequest,
-        responses::upload_file_chunk_response::UploadFileChunkResponse,
+    models::{
+        entities::{
+            file_upload_status::FileUploadStatus,
+            service_errors::{ServiceError, ServiceErrorKind},
+        },
+        view_models::{
+            requests::upload_file_chunk_request::UploadFileChunkRequest,
+            responses::{
+                abort_upload_response::AbortUploadResponse,
+                health_response::{DependencyHealth, HealthResponse, HealthStatus},
+                problem_details_response::ProblemDetailsResponse,
+                upload_file_chunk_response::UploadFileChunkResponse,
+                upload_file_chunks_response::{UploadFileChunkResult, UploadFileChunksResponse},
+                upload_file_response::UploadFileResponse,
+                upload_status_response::UploadStatusResponse,
+            },
+        },
     },
-    shared_reconciler_rust_libraries::models::entities::{
-        app_errors::{AppError, AppErrorKind},
-        file_upload_chunk::FileUploadChunkSource,
+    services::upload_drain::UploadDrain,
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunkSource,
+    web_api::{
+        handlers::{
+            abort_upload, check_liveness, check_readiness, get_metrics, get_upload_status,
+            upload_file, upload_file_chunk, upload_file_chunks,
+        },
+        utils::CORRELATION_ID_HEADER,
     },
-    web_api::handlers::upload_file_chunk,
 };
 
 #[actix_web::test]
@@ -28,6 +51,7 @@ async fn test_upload_file_chunk_calls_correct_dependencies_and_returns_success()
         mock_service.expect_upload_file_chunk().returning(|_y| {
             Ok(UploadFileChunkResponse {
                 file_chunk_id: String::from("FILE-CHUNK-1"),
+                missing_chunk_sequence_numbers: None,
             })
         });
 
@@ -57,8 +81,8 @@ async fn test_upload_file_chunk_when_invalid_request_returns_bad_request() {
         let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
 
         mock_service.expect_upload_file_chunk().returning(|_y| {
-            Err(AppError::new(
-                AppErrorKind::BadClientRequest,
+            Err(ServiceError::new(
+                ServiceErrorKind::BadClientRequest,
                 "invalid request".to_string(),
             ))
         });
@@ -82,6 +106,37 @@ async fn test_upload_file_chunk_when_invalid_request_returns_bad_request() {
     assert!(resp.status().is_client_error());
 }
 
+#[actix_web::test]
+async fn test_upload_file_chunk_when_chunk_conflicts_returns_conflict() {
+    let mut app = test::init_service((move || {
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_upload_file_chunk().returning(|_y| {
+            Err(ServiceError::new(
+                ServiceErrorKind::Conflict,
+                "chunk 1 was already received with different content".to_string(),
+            ))
+        });
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service))
+            .service(upload_file_chunk)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file-chunk")
+        .set_json(get_dummy_request())
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status().as_u16(), 409);
+    let problem: ProblemDetailsResponse = test::read_body_json(resp).await;
+    assert_eq!(problem.code, "CONFLICT");
+}
+
 #[actix_web::test]
 async fn test_upload_file_chunk_when_service_returns_error_returns_internal_error() {
     let mut app = test::init_service((move || {
@@ -89,8 +144,8 @@ async fn test_upload_file_chunk_when_service_returns_error_returns_internal_erro
         let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
 
         mock_service.expect_upload_file_chunk().returning(|_y| {
-            Err(AppError::new(
-                AppErrorKind::InternalError,
+            Err(ServiceError::new(
+                ServiceErrorKind::InternalError,
                 "Internal server error".to_string(),
             ))
         });
@@ -113,6 +168,438 @@ async fn test_upload_file_chunk_when_service_returns_error_returns_internal_erro
     assert!(resp.status().is_server_error());
 }
 
+#[actix_web::test]
+async fn test_upload_file_chunks_returns_a_result_per_chunk() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service
+            .expect_upload_file_chunks()
+            .withf(|requests| requests.len() == 2)
+            .returning(|requests| {
+                Ok(UploadFileChunksResponse {
+                    upload_request_id: requests[0].upload_request_id.clone(),
+                    chunk_results: requests
+                        .iter()
+                        .map(|request| UploadFileChunkResult {
+                            chunk_sequence_number: request.chunk_sequence_number,
+                            chunk_source: request.chunk_source,
+                            file_chunk_id: Some(format!(
+                                "FILE-CHUNK-{}",
+                                request.chunk_sequence_number
+                            )),
+                            missing_chunk_sequence_numbers: None,
+                            error: None,
+                            code: None,
+                            invalid_fields: vec![],
+                        })
+                        .collect(),
+                })
+            });
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .service(upload_file_chunks)
+    })())
+        .await;
+
+    let mut second_request = get_dummy_request();
+    second_request.chunk_sequence_number = 2;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file-chunks")
+        .set_json(vec![get_dummy_request(), second_request])
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+
+    let body: UploadFileChunksResponse = test::read_body_json(resp).await;
+    assert_eq!(body.chunk_results.len(), 2);
+}
+
+#[actix_web::test]
+async fn test_upload_file_uploads_each_chunk_of_the_file_and_returns_success() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service
+            .expect_upload_file_chunk_of_file()
+            .times(2)
+            .returning(|y, _| {
+                Ok(UploadFileChunkResponse {
+                    file_chunk_id: format!("FILE-CHUNK-{}", y.chunk_sequence_number),
+                    missing_chunk_sequence_numbers: None,
+                })
+            });
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(UploadDrain::new()))
+            .service(upload_file)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file?upload_request_id=TEST-UPLOAD-1&chunk_source=PrimaryFileChunk&chunk_size=2")
+        .set_payload("0001, 20000\n0002, 30000\n0003, 40000\n")
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+
+    let body: UploadFileResponse = test::read_body_json(resp).await;
+    assert_eq!(body.total_rows, 3);
+    assert_eq!(body.file_chunk_ids, vec!["FILE-CHUNK-1", "FILE-CHUNK-2"]);
+}
+
+#[actix_web::test]
+async fn test_upload_file_when_chunk_size_is_invalid_returns_bad_request() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_upload_file_chunk_of_file().never();
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(UploadDrain::new()))
+            .service(upload_file)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file?upload_request_id=TEST-UPLOAD-1&chunk_source=PrimaryFileChunk&chunk_size=0")
+        .set_payload("0001, 20000\n")
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_client_error());
+
+    let body: ProblemDetailsResponse = test::read_body_json(resp).await;
+    assert_eq!(body.code, "BAD_CLIENT_REQUEST");
+    assert_eq!(body.invalid_fields, vec!["chunk_size"]);
+    assert!(!body.detail.starts_with("invalid fields"));
+}
+
+#[actix_web::test]
+async fn test_upload_file_when_row_is_not_utf8_returns_problem_with_row_number() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_upload_file_chunk_of_file().never();
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(UploadDrain::new()))
+            .service(upload_file)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file?upload_request_id=TEST-UPLOAD-1&chunk_source=PrimaryFileChunk")
+        .set_payload(b"0001, 20000\n0002, \xff\n".to_vec())
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_client_error());
+
+    let body: ProblemDetailsResponse = test::read_body_json(resp).await;
+    assert_eq!(body.row_number, Some(2));
+    assert_eq!(body.detail, "this row of the uploaded file is not valid UTF-8");
+}
+
+#[actix_web::test]
+async fn test_upload_file_when_shutting_down_returns_service_unavailable() {
+    let upload_drain = Data::new(UploadDrain::new());
+    upload_drain.drain(Duration::ZERO).await;
+
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_upload_file_chunk_of_file().never();
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(upload_drain)
+            .service(upload_file)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file?upload_request_id=TEST-UPLOAD-1&chunk_source=PrimaryFileChunk")
+        .set_payload("0001, 20000\n")
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status().as_u16(), 503);
+
+    let body: ProblemDetailsResponse = test::read_body_json(resp).await;
+    assert_eq!(body.code, "SHUTTING_DOWN");
+}
+
+#[actix_web::test]
+async fn test_upload_file_chunk_when_service_returns_error_returns_problem_details() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_upload_file_chunk().returning(|_y| {
+            Err(ServiceError::new(
+                ServiceErrorKind::InternalError,
+                "Internal server error".to_string(),
+            ))
+        });
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .service(upload_file_chunk)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file-chunk")
+        .insert_header((CORRELATION_ID_HEADER, "TEST-CORRELATION-1"))
+        .set_json(get_dummy_request())
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_server_error());
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    assert_eq!(
+        resp.headers().get(CORRELATION_ID_HEADER).unwrap(),
+        "TEST-CORRELATION-1"
+    );
+
+    let body: ProblemDetailsResponse = test::read_body_json(resp).await;
+    assert_eq!(body.status, 500);
+    assert_eq!(body.code, "INTERNAL_ERROR");
+    assert_eq!(body.correlation_id, "TEST-CORRELATION-1");
+    assert_eq!(body.instance, "/upload-file-chunk");
+}
+
+#[actix_web::test]
+async fn test_upload_file_chunk_when_dependency_cant_be_reached_returns_service_unavailable() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_upload_file_chunk().returning(|_y| {
+            Err(ServiceError::new(
+                ServiceErrorKind::ConnectionError,
+                "the ledger kept changing, please retry".to_string(),
+            ))
+        });
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .service(upload_file_chunk)
+    })())
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/upload-file-chunk")
+        .set_json(get_dummy_request())
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status().as_u16(), 503);
+
+    let body: ProblemDetailsResponse = test::read_body_json(resp).await;
+    assert_eq!(body.code, "CONNECTION_ERROR");
+}
+
+#[actix_web::test]
+async fn test_get_upload_status_returns_status_of_each_file() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service
+            .expect_get_upload_status()
+            .withf(|upload_request_id| upload_request_id == "TEST-UPLOAD-1")
+            .returning(|upload_request_id| {
+                Ok(UploadStatusResponse {
+                    upload_request_id,
+                    file_upload_statuses: vec![FileUploadStatus {
+                        chunk_source: FileUploadChunkSource::PrimaryFileChunk,
+                        chunks_received: 2,
+                        highest_chunk_sequence_number: Some(3),
+                        missing_chunk_sequence_numbers: vec![2],
+                        missing_chunk_count: 1,
+                        rows_accepted: 20,
+                        rows_failed: 0,
+                        has_last_chunk_arrived: true,
+                        is_aborted: false,
+                    }],
+                })
+            });
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .service(get_upload_status)
+    })())
+        .await;
+
+    let resp = TestRequest::get()
+        .uri("/uploads/TEST-UPLOAD-1")
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+
+    let body: UploadStatusResponse = test::read_body_json(resp).await;
+    assert_eq!(body.file_upload_statuses[0].missing_chunk_sequence_numbers, vec![2]);
+}
+
+#[actix_web::test]
+async fn test_get_upload_status_when_upload_is_unknown_returns_not_found() {
+    let mut app = test::init_service((move || {
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service.expect_get_upload_status().returning(|_| {
+            Err(ServiceError::new(
+                ServiceErrorKind::NotFound,
+                "no upload found".to_string(),
+            ))
+        });
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service))
+            .service(get_upload_status)
+    })())
+        .await;
+
+    let resp = TestRequest::get()
+        .uri("/uploads/UNKNOWN-UPLOAD")
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[actix_web::test]
+async fn test_abort_upload_aborts_the_file_named_in_the_query() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkUploadServiceInterface::new());
+
+        mock_service
+            .expect_abort_upload()
+            .withf(|upload_request_id, chunk_source| {
+                upload_request_id == "TEST-UPLOAD-1"
+                    && matches!(chunk_source, FileUploadChunkSource::PrimaryFileChunk)
+            })
+            .returning(|upload_request_id, chunk_source| {
+                Ok(AbortUploadResponse {
+                    upload_request_id,
+                    chunk_source,
+                    date_aborted: 1,
+                })
+            });
+
+        let service: Box<dyn FileChunkUploadServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .service(abort_upload)
+    })())
+        .await;
+
+    let resp = TestRequest::delete()
+        .uri("/uploads/TEST-UPLOAD-1?source=PrimaryFileChunk")
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_check_liveness_returns_success() {
+    let mut app = test::init_service(App::new().service(check_liveness)).await;
+
+    let resp = TestRequest::get()
+        .uri("/health/live")
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_check_readiness_when_a_dependency_is_down_returns_service_unavailable() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_health_service = Box::new(MockHealthServiceInterface::new());
+
+        mock_health_service.expect_check_readiness().returning(|| HealthResponse {
+            status: HealthStatus::Unhealthy,
+            dependencies: vec![DependencyHealth {
+                name: String::from("dapr-sidecar"),
+                status: HealthStatus::Unhealthy,
+                latency_millis: 3,
+                error: Some(String::from("connection refused")),
+            }],
+        });
+
+        let health_service: Box<dyn HealthServiceInterface> = mock_health_service;
+
+        App::new()
+            .app_data(Data::new(health_service)) // add shared state
+            .service(check_readiness)
+    })())
+        .await;
+
+    let resp = TestRequest::get()
+        .uri("/health/ready")
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status().as_u16(), 503);
+
+    let body: HealthResponse = test::read_body_json(resp).await;
+    assert_eq!(body.dependencies[0].name, "dapr-sidecar");
+}
+
+#[actix_web::test]
+async fn test_get_metrics_returns_prometheus_text() {
+    let mut app = test::init_service(App::new().service(get_metrics)).await;
+
+    let resp = TestRequest::get()
+        .uri("/metrics")
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/plain; version=0.0.4"
+    );
+}
+
 fn get_dummy_request() -> UploadFileChunkRequest {
     UploadFileChunkRequest {
         upload_request_id: String::from("TEST-UPLOAD-1"),
@@ -120,5 +607,7 @@ fn get_dummy_request() -> UploadFileChunkRequest {
         chunk_source: FileUploadChunkSource::ComparisonFileChunk,
         chunk_rows: vec![],
         is_last_chunk: false,
+        file_hash: None,
+        is_file_hash_computed: false,
     }
 }
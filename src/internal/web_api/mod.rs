@@ -1,5 +1,17 @@
+pub mod app_settings;
+pub mod correlation_id;
 pub mod handlers;
+pub mod request_tracing;
 pub mod server;
+pub mod utils;
+
+#[cfg(test)]
+#[path = "./app_settings_tests.rs"]
+mod app_settings_tests;
+
+#[cfg(test)]
+#[path = "./correlation_id_tests.rs"]
+mod correlation_id_tests;
 
 #[cfg(test)]
 #[path = "./handlers_tests.rs"]
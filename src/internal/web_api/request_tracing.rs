@@ -0,0 +1,72 @@
+use std::future::Future;
+
+use std::time::Instant;
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse},
+    HttpMessage,
+};
+use opentelemetry::trace::FutureExt;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::internal::{
+    telemetry::trace_context::extract_trace_context, web_api::correlation_id::CorrelationId,
+};
+
+/**
+wraps every request in a span that carries on the trace of the caller, if it sent a traceparent,
+and logs how the request went once it is done
+
+the caller's trace context is also kept in the request extensions and made current while the
+request is handled, since the span only carries it when a tracing exporter is set
+ */
+pub fn trace_request<S, B>(
+    request: ServiceRequest,
+    service: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, actix_web::Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+{
+    //the route pattern rather than the path, so ids dont end up in span names
+    let http_route = request
+        .match_pattern()
+        .unwrap_or_else(|| request.path().to_string());
+
+    let correlation_id = request
+        .extensions()
+        .get::<CorrelationId>()
+        .map(|correlation_id| correlation_id.0.clone())
+        .unwrap_or_default();
+
+    let request_span = tracing::info_span!(
+        "http_request",
+        http.method = %request.method(),
+        http.route = %http_route,
+        http.status_code = tracing::field::Empty,
+        correlation_id = %correlation_id,
+    );
+    let trace_context = extract_trace_context(request.headers());
+    request_span.set_parent(trace_context.clone());
+    request.extensions_mut().insert(trace_context.clone());
+
+    let started_at = Instant::now();
+    let response_future = service.call(request);
+    let response_span = request_span.clone();
+
+    return async move {
+        let response = response_future.await?;
+        let status_code = response.status().as_u16();
+
+        response_span.record("http.status_code", status_code);
+        tracing::info!(
+            status_code,
+            duration_millis = started_at.elapsed().as_millis() as u64,
+            "request handled"
+        );
+
+        return Ok(response);
+    }
+    .instrument(request_span)
+    .with_context(trace_context);
+}
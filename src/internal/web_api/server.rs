@@ -1,100 +1,256 @@
-use actix_web::{App, HttpServer, web::Data};
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{dev::ServerHandle, App, HttpServer, web::{self, Data}};
 
 use crate::{
     external::{
-        connectors::recon_tasks_service_connector::ReconTasksServiceConnector,
-        pubsub::dapr_pubsub::DaprPubSub,
+        connectors::{
+            caching_recon_tasks_retriever::CachingReconTasksRetriever,
+            recon_tasks_service_connector::ReconTasksServiceConnector,
+            recon_tasks_service_health_check::ReconTasksServiceHealthCheck,
+        },
+        dapr_sidecar::{
+            dapr_connection::DaprConnection, dapr_sidecar_health_check::DaprSidecarHealthCheck,
+        },
+        pubsub::{
+            dapr_pubsub::DaprPubSub,
+            retrying_pubsub::{RetryingPubSub, RetryPolicy},
+        },
+        state_store::dapr_state_store_chunk_ledger::DaprStateStoreChunkLedger,
     },
     internal::{
-        interfaces::file_chunk_upload_service::FileChunkUploadServiceInterface,
+        interfaces::{
+            file_chunk_upload_service::FileChunkUploadServiceInterface,
+            health_check::HealthServiceInterface,
+        },
         services::{
-            core_logic::transformer::Transformer, file_upload_service::FileChunkUploadService,
+            core_logic::transformer::Transformer,
+            draining_file_upload_service::DrainingFileChunkUploadService,
+            file_upload_service::FileChunkUploadService,
+            health_service::HealthService,
+            upload_drain::{UploadDrain, UploadDrainHealthCheck},
+        },
+        telemetry::tracing_setup::{init_tracing, shutdown_tracing},
+        web_api::{
+            app_settings::{load_app_settings, AppSettings},
+            correlation_id, handlers, request_tracing, utils,
         },
-        web_api::handlers,
     },
 };
 use crate::internal::shared_reconciler_rust_libraries::sdks::internal_microservices::interfaces::recon_tasks_microservice::ReconTasksMicroserviceClientInterface;
 use crate::internal::shared_reconciler_rust_libraries::sdks::internal_microservices::recon_tasks_microservice::ReconTasksMicroserviceClient;
 
 // constants
-const DEFAULT_DAPR_CONNECTION_URL: &'static str = "http://localhost:5006";
-const DEFAULT_DAPR_PUBSUB_NAME: &'static str = "pubsub";
-const DEFAULT_APP_LISTEN_IP: &'static str = "0.0.0.0";
-const DEFAULT_APP_LISTEN_PORT: u16 = 8084;
-const DEFAULT_RECON_TASKS_CONNECTION_URL: &'static str = "http://localhost:3600";
-const DEFAULT_RECON_TASKS_SERVICE_ID: &'static str = "svc-task-details-repository-manager";
+const DAPR_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+//once uploads are drained, how long the remaining requests (status reads, probes) get
+const SERVER_SHUTDOWN_TIMEOUT_SECS: u64 = 5;
 
-#[derive(Clone, Debug)]
-struct AppSettings {
-    pub app_port: String,
+pub async fn run_async() -> Result<(), std::io::Error> {
+    //retrieve app settings from the defaults, config file, env variables and CLI flags
+    let app_settings = match load_app_settings() {
+        Ok(app_settings) => app_settings,
+        Err(e) => {
+            //logging is configured by these very settings, so this has to go straight to stderr
+            eprintln!("{}", e.message);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e.message));
+        }
+    };
 
-    pub app_ip: String,
+    //logs and spans go nowhere until this is done
+    if let Err(e) = init_tracing(&app_settings.tracing_settings) {
+        return Err(std::io::Error::other(e.message));
+    }
 
-    pub dapr_pubsub_name: String,
+    let effective_config = serde_json::to_string(&app_settings.redacted()).unwrap_or_default();
+    tracing::info!(effective_config = %effective_config, "loaded configuration");
 
-    pub dapr_pubsub_server_address: String,
+    let app_listen_address = (app_settings.app_ip, app_settings.app_port);
 
-    pub recon_tasks_service_name: String,
+    //the service graph is built once and shared by every worker, so the dapr channel,
+    //recon task cache and upload drain are process wide rather than per thread
+    let dapr_connection = Arc::new(DaprConnection::new(
+        app_settings.dapr_pubsub_server_address.clone(),
+    ));
+    dapr_connection.start_health_checks(DAPR_HEALTH_CHECK_INTERVAL);
 
-    pub recon_tasks_connection_url: String,
-}
-
-pub async fn run_async() -> Result<(), std::io::Error> {
-    //retrieve app settings from the env variables
-    let app_settings = read_app_settings();
+    let upload_drain = Arc::new(UploadDrain::new());
 
-    let app_listen_url = format!("{}:{}", app_settings.app_ip, app_settings.app_port);
-
-    //just for logging purposes
-    println!("App is listening on: {:?}", app_listen_url);
-
-    HttpServer::new(move || {
-        // Create some global state prior to running the handler threads
-        let service = setup_service(app_settings.clone());
+    let service = Data::new(setup_service(
+        app_settings.clone(),
+        dapr_connection.clone(),
+        upload_drain.clone(),
+    ));
+    let health_service = Data::new(setup_health_service(
+        app_settings.clone(),
+        dapr_connection,
+        upload_drain.clone(),
+    ));
+    let upload_drain_data = Data::from(upload_drain.clone());
 
+    let server = HttpServer::new(move || {
         // add shared state and routing
         App::new()
-            .app_data(Data::new(service))
+            .wrap_fn(request_tracing::trace_request)
+            //registered last so it runs first, and the request span can pick the id up
+            .wrap_fn(correlation_id::assign_correlation_id)
+            .app_data(service.clone())
+            .app_data(health_service.clone())
+            .app_data(upload_drain_data.clone())
+            //malformed bodies and query strings get a problem body like every other error
+            .app_data(web::JsonConfig::default().error_handler(utils::json_error_handler))
+            .app_data(web::QueryConfig::default().error_handler(utils::query_error_handler))
             .service(handlers::upload_file_chunk)
+            .service(handlers::upload_file_chunks)
+            .service(handlers::upload_file)
+            .service(handlers::get_upload_status)
+            .service(handlers::abort_upload)
+            .service(handlers::check_liveness)
+            .service(handlers::check_readiness)
+            .service(handlers::get_metrics)
     })
-        .bind(app_listen_url)?
-        .run()
-        .await
+        //shutdown signals are handled by us, so uploads are drained before the listener closes
+        .disable_signals()
+        .shutdown_timeout(SERVER_SHUTDOWN_TIMEOUT_SECS)
+        .bind(app_listen_address)?
+        .run();
+
+    //only once the address is bound, so the line cant be logged for a server that never started
+    tracing::info!(app_listen_url = ?app_listen_address, "App is listening");
+
+    tokio::spawn(drain_on_shutdown_signal(
+        server.handle(),
+        upload_drain,
+        Duration::from_millis(app_settings.shutdown_drain_deadline_millis),
+    ));
+
+    let run_result = server.await;
+
+    shutdown_tracing();
+
+    return run_result;
 }
 
-fn setup_service(app_settings: AppSettings) -> Box<dyn FileChunkUploadServiceInterface> {
+/**
+on SIGTERM (or ctrl-c) takes the instance out of rotation, gives in-flight uploads until the
+deadline to finish, logs any it had to abandon and then stops the server
+ */
+async fn drain_on_shutdown_signal(
+    server_handle: ServerHandle,
+    upload_drain: Arc<UploadDrain>,
+    drain_deadline: Duration,
+) {
+    wait_for_shutdown_signal().await;
+
+    tracing::info!(
+        in_flight_uploads = upload_drain.in_flight_uploads().len(),
+        drain_deadline_millis = drain_deadline.as_millis() as u64,
+        "shutting down, draining in-flight uploads"
+    );
+
+    let abandoned_uploads = upload_drain.drain(drain_deadline).await;
+
+    if abandoned_uploads.is_empty() {
+        tracing::info!("all in-flight uploads finished");
+    } else {
+        tracing::warn!(
+            abandoned_uploads = ?abandoned_uploads,
+            "in-flight uploads abandoned at the drain deadline"
+        );
+    }
+
+    //uploads we already gave up on are not worth waiting for a second time
+    server_handle.stop(abandoned_uploads.is_empty()).await;
+}
+
+#[cfg(unix)]
+async fn wait_for_shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigterm = match signal(SignalKind::terminate()) {
+        Ok(sigterm) => sigterm,
+        Err(e) => {
+            tracing::error!(error = %e, "cant listen for SIGTERM, only ctrl-c will drain uploads");
+            let _ = tokio::signal::ctrl_c().await;
+            return;
+        }
+    };
+
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+fn setup_service(
+    app_settings: AppSettings,
+    dapr_connection: Arc<DaprConnection>,
+    upload_drain: Arc<UploadDrain>,
+) -> Box<dyn FileChunkUploadServiceInterface> {
     let recon_tasks_ms_client: Box<dyn ReconTasksMicroserviceClientInterface> = Box::new(ReconTasksMicroserviceClient {
         host: app_settings.recon_tasks_connection_url.clone(),
         recon_tasks_service_app_id: app_settings.recon_tasks_service_name.clone(),
     });
-    let service: Box<dyn FileChunkUploadServiceInterface> = Box::new(FileChunkUploadService {
-        file_upload_repo: Box::new(DaprPubSub {
-            dapr_grpc_server_address: app_settings.dapr_pubsub_server_address.clone(),
-            dapr_pubsub_name: app_settings.dapr_pubsub_name.clone(),
+    //one ledger for both repos, so the upload status reads the ledger that chunks are recorded in
+    let chunk_ledger = DaprStateStoreChunkLedger::new(
+        dapr_connection.clone(),
+        app_settings.dapr_state_store_name.clone(),
+    );
+    let upload_service: Box<dyn FileChunkUploadServiceInterface> = Box::new(FileChunkUploadService {
+        file_upload_repo: Box::new(RetryingPubSub {
+            pubsub: Box::new(DaprPubSub {
+                dapr_connection: dapr_connection.clone(),
+                dapr_pubsub_name: app_settings.dapr_pubsub_name.clone(),
+            }),
+            retry_policy: RetryPolicy {
+                max_attempts: app_settings.pubsub_retry_max_attempts,
+                base_delay: Duration::from_millis(app_settings.pubsub_retry_base_delay_millis),
+                max_delay: Duration::from_millis(app_settings.pubsub_retry_max_delay_millis),
+                jitter: app_settings.pubsub_retry_jitter,
+            },
         }),
 
-        recon_tasks_retriever: Box::new(ReconTasksServiceConnector::new(recon_tasks_ms_client)),
+        chunk_ledger_repo: Box::new(chunk_ledger.clone()),
+        upload_status_repo: Box::new(chunk_ledger),
+
+        recon_tasks_retriever: Box::new(CachingReconTasksRetriever::new(
+            Box::new(ReconTasksServiceConnector::new(recon_tasks_ms_client)),
+            Duration::from_secs(app_settings.recon_tasks_cache_ttl_seconds),
+            app_settings.recon_tasks_cache_max_entries,
+        )),
         to_entity_transformer: Box::new(Transformer {}),
+        file_upload_completed_topic: app_settings.file_upload_completed_topic.clone(),
+        file_upload_verification_topic: app_settings.file_upload_verification_topic.clone(),
+    });
+    let service: Box<dyn FileChunkUploadServiceInterface> = Box::new(DrainingFileChunkUploadService {
+        inner_service: upload_service,
+        upload_drain,
     });
     service
 }
 
-fn read_app_settings() -> AppSettings {
-    AppSettings {
-        app_port: std::env::var("APP_PORT").unwrap_or(DEFAULT_APP_LISTEN_PORT.to_string()),
-
-        app_ip: std::env::var("APP_IP").unwrap_or(DEFAULT_APP_LISTEN_IP.to_string()),
-
-        dapr_pubsub_name: std::env::var("DAPR_PUBSUB_NAME")
-            .unwrap_or(DEFAULT_DAPR_PUBSUB_NAME.to_string()),
-
-        dapr_pubsub_server_address: std::env::var("DAPR_IP")
-            .unwrap_or(DEFAULT_DAPR_CONNECTION_URL.to_string()),
-
-        recon_tasks_service_name: std::env::var("RECON_TASKS_SERVICE_NAME")
-            .unwrap_or(DEFAULT_RECON_TASKS_SERVICE_ID.to_string()),
-
-        recon_tasks_connection_url: std::env::var("RECON_TASKS_SERVICE_HOST")
-            .unwrap_or(DEFAULT_RECON_TASKS_CONNECTION_URL.to_string()),
-    }
+fn setup_health_service(
+    app_settings: AppSettings,
+    dapr_connection: Arc<DaprConnection>,
+    upload_drain: Arc<UploadDrain>,
+) -> Box<dyn HealthServiceInterface> {
+    let health_service: Box<dyn HealthServiceInterface> = Box::new(HealthService {
+        dependency_health_checks: vec![
+            Box::new(UploadDrainHealthCheck { upload_drain }),
+            Box::new(DaprSidecarHealthCheck { dapr_connection }),
+            Box::new(ReconTasksServiceHealthCheck {
+                host: app_settings.recon_tasks_connection_url.clone(),
+                recon_tasks_service_app_id: app_settings.recon_tasks_service_name.clone(),
+                health_check_path: app_settings.recon_tasks_health_check_path.clone(),
+                http_client: reqwest::Client::new(),
+            }),
+        ],
+        health_check_timeout: Duration::from_millis(app_settings.health_check_timeout_millis),
+    });
+    health_service
 }
@@ -0,0 +1,156 @@
+use actix_web::{
+    error::{InternalError, JsonPayloadError, QueryPayloadError},
+    http::StatusCode,
+    HttpMessage, HttpRequest, HttpResponse,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::internal::{
+    models::{
+        entities::service_errors::{ServiceError, ServiceErrorKind},
+        view_models::responses::problem_details_response::ProblemDetailsResponse,
+    },
+    web_api::correlation_id::CorrelationId,
+};
+
+pub const CORRELATION_ID_HEADER: &'static str = "X-Correlation-Id";
+const PROBLEM_JSON_CONTENT_TYPE: &'static str = "application/problem+json";
+const PROBLEM_TYPE_PREFIX: &'static str = "/problems/";
+//long or odd correlation ids are replaced rather than written into our logs
+const MAX_CORRELATION_ID_LENGTH: usize = 128;
+
+/**
+returns the value as json on success, otherwise an RFC 7807 problem describing the error
+ */
+pub fn ok_or_problem<T: Serialize>(request: &HttpRequest, result: Result<T, ServiceError>) -> HttpResponse {
+    return match result {
+        Ok(value) => HttpResponse::Ok().json(value),
+        Err(e) => problem_response(request, &e),
+    };
+}
+
+/**
+like ok_or_problem, for routes whose failures were already logged with more to say about
+them than the problem response has, so that each failure is logged once
+ */
+pub fn ok_or_logged_problem<T: Serialize>(
+    request: &HttpRequest,
+    result: Result<T, ServiceError>,
+) -> HttpResponse {
+    return match result {
+        Ok(value) => HttpResponse::Ok().json(value),
+        Err(e) => unlogged_problem_response(request, &e),
+    };
+}
+
+pub fn problem_response(request: &HttpRequest, service_error: &ServiceError) -> HttpResponse {
+    log_problem(service_error);
+
+    return unlogged_problem_response(request, service_error);
+}
+
+/**
+logs the error the way a problem response for it would be logged
+ */
+pub fn log_problem(service_error: &ServiceError) {
+    if status_of(service_error) >= StatusCode::INTERNAL_SERVER_ERROR {
+        tracing::error!(
+            error_kind = ?service_error.kind,
+            error = %service_error.message,
+            "request failed"
+        );
+    } else {
+        tracing::warn!(
+            error_kind = ?service_error.kind,
+            error = %service_error.message,
+            "request rejected"
+        );
+    }
+}
+
+fn unlogged_problem_response(request: &HttpRequest, service_error: &ServiceError) -> HttpResponse {
+    let problem_details = to_problem_details(request, service_error);
+
+    let status = StatusCode::from_u16(problem_details.status)
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    return HttpResponse::build(status)
+        .content_type(PROBLEM_JSON_CONTENT_TYPE)
+        .insert_header((CORRELATION_ID_HEADER, problem_details.correlation_id.clone()))
+        .json(problem_details);
+}
+
+/**
+a request body that cant be read as json gets the same problem body as any other bad request
+ */
+pub fn json_error_handler(error: JsonPayloadError, request: &HttpRequest) -> actix_web::Error {
+    let service_error = ServiceError::new(ServiceErrorKind::BadClientRequest, error.to_string());
+    let problem = problem_response(request, &service_error);
+
+    return InternalError::from_response(error, problem).into();
+}
+
+pub fn query_error_handler(error: QueryPayloadError, request: &HttpRequest) -> actix_web::Error {
+    let service_error = ServiceError::new(ServiceErrorKind::BadClientRequest, error.to_string());
+    let problem = problem_response(request, &service_error);
+
+    return InternalError::from_response(error, problem).into();
+}
+
+/**
+the correlation id already given to the request, else the one the caller sent us,
+else a new one
+ */
+pub fn correlation_id_of(request: &HttpRequest) -> String {
+    if let Some(correlation_id) = request.extensions().get::<CorrelationId>() {
+        return correlation_id.0.clone();
+    }
+
+    return request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|header_value| header_value.to_str().ok())
+        .filter(|correlation_id| is_usable_correlation_id(correlation_id))
+        .map(|correlation_id| correlation_id.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+}
+
+fn is_usable_correlation_id(correlation_id: &str) -> bool {
+    return !correlation_id.is_empty()
+        && correlation_id.len() <= MAX_CORRELATION_ID_LENGTH
+        && correlation_id
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_.:".contains(character));
+}
+
+fn to_problem_details(request: &HttpRequest, service_error: &ServiceError) -> ProblemDetailsResponse {
+    let status = status_of(service_error);
+    let code = service_error.kind.code().to_string();
+
+    return ProblemDetailsResponse {
+        problem_type: format!("{}{}", PROBLEM_TYPE_PREFIX, code.to_lowercase().replace('_', "-")),
+        title: status.canonical_reason().unwrap_or("Error").to_string(),
+        status: status.as_u16(),
+        detail: service_error.message.clone(),
+        instance: request.path().to_string(),
+        code,
+        correlation_id: correlation_id_of(request),
+        invalid_fields: service_error.invalid_fields.clone(),
+        row_number: service_error.row_number,
+    };
+}
+
+fn status_of(service_error: &ServiceError) -> StatusCode {
+    return match service_error.kind {
+        ServiceErrorKind::BadClientRequest => StatusCode::BAD_REQUEST,
+        ServiceErrorKind::Conflict => StatusCode::CONFLICT,
+        //a dependency that cant be reached, or a ledger that kept changing, is worth retrying
+        ServiceErrorKind::ConnectionError => StatusCode::SERVICE_UNAVAILABLE,
+        ServiceErrorKind::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        ServiceErrorKind::NotFound => StatusCode::NOT_FOUND,
+        //a draining instance answers 503 so that clients and load balancers retry elsewhere
+        ServiceErrorKind::ShuttingDown => StatusCode::SERVICE_UNAVAILABLE,
+        ServiceErrorKind::UploadAborted => StatusCode::GONE,
+    };
+}